@@ -0,0 +1,69 @@
+//! Measures how the interaction pipeline scales with the number of interactables.
+//!
+//! Run with `cargo bench --bench interaction`.
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use retro_2d_lib::interact::testing::{spawn_headless_window, HeadlessPlugin};
+use retro_2d_lib::{Interactable, InteractionShape, InteractionSource, InteractionState};
+
+const FRAMES: u32 = 200;
+
+fn build_app(count: usize) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin, retro_2d_lib::InteractionPlugin));
+    let window = spawn_headless_window(app.world_mut(), Vec2::new(1280.0, 900.0));
+    app.world_mut()
+        .spawn((Camera2d, InteractionSource::default()));
+
+    // A square grid of items, 40 units apart
+    let side = (count as f32).sqrt().ceil() as usize;
+    for i in 0..count {
+        let position = Vec2::new((i % side) as f32, (i / side) as f32) * 40.0
+            - Vec2::splat(side as f32 * 20.0);
+        app.world_mut().spawn((
+            Transform::from_translation(position.extend(i as f32 * 0.001)),
+            Interactable {
+                shape: InteractionShape::rect(Vec2::splat(48.0)),
+                ..default()
+            },
+        ));
+    }
+    app.update();
+    (app, window)
+}
+
+fn run(count: usize) -> (Duration, usize) {
+    let (mut app, window) = build_app(count);
+    let mut hits = 0;
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        let t = frame as f32 / FRAMES as f32 * std::f32::consts::TAU;
+        app.world_mut().send_event(CursorMoved {
+            window,
+            position: Vec2::new(640.0 + t.cos() * 300.0, 450.0 + t.sin() * 300.0),
+            delta: None,
+        });
+        app.update();
+        hits += app
+            .world()
+            .resource::<InteractionState>()
+            .ordered_interact_list_map
+            .values()
+            .map(Vec::len)
+            .sum::<usize>();
+    }
+    (start.elapsed() / FRAMES, hits)
+}
+
+fn main() {
+    println!(
+        "{:>12} {:>14} {:>8}",
+        "interactables", "time / frame", "hits"
+    );
+    for count in [100, 1_000, 10_000] {
+        let (frame_time, hits) = run(count);
+        println!("{count:>12} {frame_time:>14.2?} {hits:>8}");
+    }
+}
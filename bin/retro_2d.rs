@@ -68,7 +68,19 @@ fn main() {
                     ..default()
                 }),
         );
+
+        // Record the input to replay bug reports headless with `InputReplay`
+        if let Ok(path) = std::env::var("RETRO_2D_RECORD_INPUT") {
+            app.add_plugins(retro_2d_lib::InputRecorderPlugin {
+                path: Some(path.into()),
+            });
+        }
     }
 
+    // The debug overlay is left out of release builds, in browsers its F3 toggle
+    // would also open the find bar
+    #[cfg(debug_assertions)]
+    app.add_plugins(retro_2d_lib::InteractionDebugPlugin);
+
     app.add_plugins(AssetsPlugin).add_plugins(WorldPlugin).run();
 }
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+/// How the cursor is tested against an interactable entity
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HitMode {
+    /// Only the shape of the interactable is tested
+    #[default]
+    Shape,
+    /// After the shape test, the alpha channel of the entity's sprite
+    /// image is sampled under the cursor. Only pixels with an alpha above the
+    /// threshold count as a hit.
+    Alpha { threshold: f32 },
+}
+
+impl HitMode {
+    pub fn alpha() -> Self {
+        Self::Alpha { threshold: 0.5 }
+    }
+}
+
+/// Samples the alpha of `sprite` at a position given in the sprite's local space
+/// (translation, rotation and scale of the entity already removed).
+///
+/// Positions outside of the sprite are fully transparent. Returns `None` if the image
+/// can't be sampled, for example because it isn't loaded yet or uses a compressed format.
+pub fn sprite_alpha_at(
+    sprite: &Sprite,
+    local_position: Vec2,
+    images: &Assets<Image>,
+    atlas_layouts: &Assets<TextureAtlasLayout>,
+) -> Option<f32> {
+    let image = images.get(&sprite.image)?;
+    if image.width() == 0 || image.height() == 0 {
+        return None;
+    }
+
+    // The region of the image the sprite renders, in pixels
+    let atlas_rect = sprite.texture_atlas.as_ref().and_then(|atlas| {
+        atlas_layouts
+            .get(&atlas.layout)
+            .and_then(|layout| layout.textures.get(atlas.index))
+            .map(|rect| rect.as_rect())
+    });
+    let texture_rect = match (atlas_rect, sprite.rect) {
+        (Some(atlas_rect), Some(mut rect)) => {
+            rect.min += atlas_rect.min;
+            rect.max += atlas_rect.min;
+            rect
+        }
+        (Some(atlas_rect), None) => atlas_rect,
+        (None, Some(rect)) => rect,
+        (None, None) => Rect::from_corners(Vec2::ZERO, image.size_f32()),
+    };
+
+    // The sprite is drawn at its custom size if it has one, shifted by its anchor
+    let size = sprite.custom_size.unwrap_or(texture_rect.size());
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return None;
+    }
+    let min = -sprite.anchor.as_vec() * size - size / 2.0;
+    let mut uv = (local_position - min) / size;
+    if !(0.0..1.0).contains(&uv.x) || !(0.0..1.0).contains(&uv.y) {
+        return Some(0.0);
+    }
+    if sprite.flip_x {
+        uv.x = 1.0 - uv.x;
+    }
+    // Image rows go from top to bottom while world y goes up
+    if !sprite.flip_y {
+        uv.y = 1.0 - uv.y;
+    }
+
+    let pixel = (texture_rect.min + uv * texture_rect.size())
+        .floor()
+        .clamp(Vec2::ZERO, image.size_f32() - Vec2::ONE);
+    image
+        .get_color_at(pixel.x as u32, pixel.y as u32)
+        .ok()
+        .map(|color| color.alpha())
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use bevy::window::SystemCursorIcon;
+use bevy::winit::cursor::CursorIcon;
+
+use super::markers::{Hovered, PressedBy};
+use crate::{Dragged, InteractionState, PointerId};
+
+/// The cursor icons shown while the mouse cursor interacts with the entity.
+/// States without an icon fall back to the previous one,
+/// so dragging shows the pressed icon and pressing the hover icon if not set.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct InteractionCursor {
+    pub hover: Option<CursorIcon>,
+    pub pressed: Option<CursorIcon>,
+    pub dragged: Option<CursorIcon>,
+}
+
+impl InteractionCursor {
+    pub fn new(hover: impl Into<CursorIcon>) -> Self {
+        Self {
+            hover: Some(hover.into()),
+            ..default()
+        }
+    }
+
+    pub fn with_pressed(mut self, pressed: impl Into<CursorIcon>) -> Self {
+        self.pressed = Some(pressed.into());
+        self
+    }
+
+    pub fn with_dragged(mut self, dragged: impl Into<CursorIcon>) -> Self {
+        self.dragged = Some(dragged.into());
+        self
+    }
+
+    /// A pointer on hover, grab and grabbing while pressed and dragged
+    pub fn grab() -> Self {
+        Self::new(SystemCursorIcon::Pointer)
+            .with_pressed(SystemCursorIcon::Grab)
+            .with_dragged(SystemCursorIcon::Grabbing)
+    }
+
+    fn hover_icon(&self) -> Option<&CursorIcon> {
+        self.hover.as_ref()
+    }
+
+    fn pressed_icon(&self) -> Option<&CursorIcon> {
+        self.pressed.as_ref().or(self.hover_icon())
+    }
+
+    fn dragged_icon(&self) -> Option<&CursorIcon> {
+        self.dragged.as_ref().or(self.pressed_icon())
+    }
+}
+
+/// The window whose cursor icon is overridden and the icon it had before
+#[derive(Default)]
+pub(crate) struct CursorOverride {
+    window: Option<Entity>,
+    previous: Option<CursorIcon>,
+}
+
+type CursorState = (
+    &'static InteractionCursor,
+    &'static GlobalTransform,
+    Option<&'static Hovered>,
+    Option<&'static PressedBy>,
+    Option<&'static Dragged>,
+);
+
+/// This system sets the cursor icon of the window the mouse is in
+/// from the `InteractionCursor` of the entity it interacts with.
+/// Dragging wins over pressing and pressing over hovering, ties go to the topmost entity.
+/// The window icon is restored once nothing with an `InteractionCursor` is hovered.
+pub(crate) fn cursor_icon_system(
+    interaction_state: Res<InteractionState>,
+    cursors: Query<CursorState>,
+    mut windows: Query<Option<&mut CursorIcon>, With<Window>>,
+    mut cursor_override: Local<CursorOverride>,
+    mut commands: Commands,
+) {
+    let window = interaction_state.last_window.filter(|window| {
+        interaction_state
+            .window_cursor_positions
+            .contains_key(window)
+    });
+
+    let mut icon: Option<(u8, f32, &CursorIcon)> = None;
+    for (cursor, global_transform, hovered, pressed, dragged) in cursors.iter() {
+        let candidate = if dragged.is_some_and(|dragged| dragged.pointer == PointerId::Mouse) {
+            cursor.dragged_icon().map(|icon| (2, icon))
+        } else if pressed.is_some_and(|pressed| pressed.pointer == PointerId::Mouse) {
+            cursor.pressed_icon().map(|icon| (1, icon))
+        } else if hovered.is_some_and(|hovered| hovered.pointer == PointerId::Mouse) {
+            cursor.hover_icon().map(|icon| (0, icon))
+        } else {
+            None
+        };
+        let Some((priority, candidate)) = candidate else {
+            continue;
+        };
+        let depth = global_transform.translation().z;
+        if icon.is_none_or(|(p, d, _)| (priority, depth) > (p, d)) {
+            icon = Some((priority, depth, candidate));
+        }
+    }
+    let icon = window.and(icon.map(|(_, _, icon)| icon.clone()));
+
+    // Restore the window we no longer override
+    if let Some(overridden) = cursor_override.window {
+        if icon.is_none() || window != Some(overridden) {
+            if let Some(mut entity_commands) = commands.get_entity(overridden) {
+                match cursor_override.previous.take() {
+                    Some(previous) => entity_commands.insert(previous),
+                    None => entity_commands.remove::<CursorIcon>(),
+                };
+            }
+            cursor_override.window = None;
+        }
+    }
+
+    let (Some(window), Some(icon)) = (window, icon) else {
+        return;
+    };
+    let Ok(current) = windows.get_mut(window) else {
+        return;
+    };
+    if cursor_override.window.is_none() {
+        cursor_override.window = Some(window);
+        cursor_override.previous = current.as_deref().cloned();
+    }
+    match current {
+        Some(mut current) => {
+            current.set_if_neq(icon);
+        }
+        None => {
+            commands.entity(window).insert(icon);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+use super::shape::InteractionShape;
+use crate::{Dragged, Group, Interactable, InteractionSet, InteractionState};
+
+/// Segments used to draw circles, so they can be skewed with their entity
+const CIRCLE_SEGMENTS: usize = 32;
+const SHAPE_COLOR: Color = Color::srgba(0.6, 0.6, 0.6, 0.6);
+const SPRITE_COLOR: Color = Color::srgba(1.0, 0.0, 1.0, 0.4);
+const DRAG_COLOR: Color = Color::WHITE;
+
+/// The debug plugin draws the interaction state with gizmos: the shape of every interactable,
+/// the hits of each group in the group color, the world cursor of each group and the active drags.
+/// The sprite bounds of interactables are drawn as well, to spot shapes not matching their sprite.
+/// It needs the `GizmoPlugin` of the `DefaultPlugins`.
+pub struct InteractionDebugPlugin;
+
+impl Plugin for InteractionDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionDebug>().add_systems(
+            Update,
+            (
+                toggle_interaction_debug_system,
+                interaction_debug_system
+                    .after(InteractionSet)
+                    .run_if(|debug: Res<InteractionDebug>| debug.enabled),
+            ),
+        );
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct InteractionDebug {
+    pub enabled: bool,
+    /// The key toggling the overlay, `None` to only toggle it from code
+    pub toggle_key: Option<KeyCode>,
+}
+
+impl Default for InteractionDebug {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            toggle_key: Some(KeyCode::F3),
+        }
+    }
+}
+
+/// The debug color of a group, neighbouring groups get distinct hues
+pub fn group_color(group: Group) -> Color {
+    Color::hsl((group.0 as f32 * 137.5) % 360.0, 0.9, 0.6)
+}
+
+fn toggle_interaction_debug_system(
+    mut debug: ResMut<InteractionDebug>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let (Some(key), Some(keys)) = (debug.toggle_key, keys) else {
+        return;
+    };
+    if keys.just_pressed(key) {
+        debug.enabled = !debug.enabled;
+    }
+}
+
+fn interaction_debug_system(
+    interaction_state: Res<InteractionState>,
+    interactables: Query<(Entity, &GlobalTransform, &Interactable, Option<&Sprite>)>,
+    dragged: Query<(&GlobalTransform, &Dragged)>,
+    images: Option<Res<Assets<Image>>>,
+    mut gizmos: Gizmos,
+) {
+    let mut hit_groups: HashMap<Entity, Group> = HashMap::new();
+    for (group, list) in interaction_state.ordered_interact_list_map.iter() {
+        for (entity, _) in list.iter() {
+            // The lowest group colors entities hit in several groups
+            hit_groups
+                .entry(*entity)
+                .and_modify(|hit_group| *hit_group = Group(hit_group.0.min(group.0)))
+                .or_insert(*group);
+        }
+    }
+
+    for (entity, global_transform, interactable, sprite) in interactables.iter() {
+        let affine = global_transform.affine();
+        let size = sprite.and_then(|sprite| {
+            sprite.custom_size.or_else(|| {
+                let image = images.as_ref()?.get(&sprite.image)?;
+                Some(image.size().as_vec2())
+            })
+        });
+        if let (Some(sprite), Some(size)) = (sprite, size) {
+            let center = -sprite.anchor.as_vec() * size;
+            let bounds = InteractionShape::Aabb {
+                min: center - size / 2.0,
+                max: center + size / 2.0,
+            };
+            draw_shape(&mut gizmos, &bounds, &affine, SPRITE_COLOR);
+        }
+        let color = hit_groups
+            .get(&entity)
+            .map(|group| group_color(*group))
+            .unwrap_or(SHAPE_COLOR);
+        draw_shape(&mut gizmos, &interactable.shape, &affine, color);
+    }
+
+    for (group, position) in interaction_state.cursor_positions.iter() {
+        gizmos.cross_2d(
+            Isometry2d::from_translation(*position),
+            8.0,
+            group_color(*group),
+        );
+    }
+
+    for (global_transform, dragged) in dragged.iter() {
+        let Some(pointer_position) =
+            interaction_state.pointer_position(dragged.pointer, dragged.group)
+        else {
+            continue;
+        };
+        let translation = global_transform.translation().truncate();
+        // Where the entity started and how it hangs on the pointer
+        gizmos.circle_2d(
+            Isometry2d::from_translation(dragged.origin),
+            6.0,
+            DRAG_COLOR,
+        );
+        gizmos.line_2d(dragged.origin, translation, DRAG_COLOR);
+        gizmos.arrow_2d(pointer_position, translation, group_color(dragged.group));
+    }
+}
+
+fn draw_shape(gizmos: &mut Gizmos, shape: &InteractionShape, affine: &Affine3A, color: Color) {
+    let to_world = |point: Vec2| affine.transform_point3(point.extend(0.0)).truncate();
+    match shape {
+        InteractionShape::Aabb { min, max } => {
+            let corners = [
+                *min,
+                Vec2::new(max.x, min.y),
+                *max,
+                Vec2::new(min.x, max.y),
+                *min,
+            ];
+            gizmos.linestrip_2d(corners.map(to_world), color);
+        }
+        InteractionShape::Circle { center, radius } => {
+            let points = (0..=CIRCLE_SEGMENTS).map(|i| {
+                let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                to_world(*center + Vec2::from_angle(angle) * *radius)
+            });
+            gizmos.linestrip_2d(points, color);
+        }
+        InteractionShape::Polygon(points) => {
+            let closed = points
+                .iter()
+                .chain(points.first())
+                .map(|point| to_world(*point));
+            gizmos.linestrip_2d(closed, color);
+        }
+        InteractionShape::Compound(shapes) => {
+            for shape in shapes {
+                draw_shape(gizmos, shape, affine, color);
+            }
+        }
+    }
+}
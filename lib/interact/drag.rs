@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 
-use crate::{Group, Interactable, InteractionState};
+use super::drop::{drop_zone_system, DragEnter, DragLeave, DragOver, DropZoneState, Dropped};
+use super::layers::GroupMask;
+use crate::{Group, Interactable, InteractionSet, InteractionState, PointerId};
 
 #[derive(Component)]
 pub struct Dragged {
     pub group: Group,
+    /// The mouse cursor or finger that is dragging the entity
+    pub pointer: PointerId,
+    /// The button holding the entity, `MouseButton::Left` for touches
+    pub button: MouseButton,
     pub translation: Vec2,
     pub origin: Vec2,
     pub just_dropped: bool,
@@ -23,14 +29,23 @@ impl Dragged {
 pub struct DragPlugin;
 impl Plugin for DragPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<InteractionState>().add_systems(
-            Update,
-            (
-                mouse_press_start_drag_system,
-                mouse_release_stop_drag_system,
-                drag_system,
-            ),
-        );
+        app.init_resource::<InteractionState>()
+            .init_resource::<DropZoneState>()
+            .add_event::<DragEnter>()
+            .add_event::<DragOver>()
+            .add_event::<DragLeave>()
+            .add_event::<Dropped>()
+            .add_systems(
+                Update,
+                (
+                    mouse_press_start_drag_system,
+                    drop_zone_system,
+                    mouse_release_stop_drag_system,
+                    drag_system,
+                )
+                    .chain()
+                    .after(InteractionSet),
+            );
     }
 }
 
@@ -42,7 +57,9 @@ pub fn drag_system(
         if dragged.just_dragged {
             dragged.just_dragged = false;
         }
-        if let Some(cursor_position) = interaction_state.cursor_positions.get(&dragged.group) {
+        if let Some(cursor_position) =
+            interaction_state.pointer_position(dragged.pointer, dragged.group)
+        {
             let new_y = if draggable.lock_y {
                 dragged.origin.y
             } else {
@@ -74,65 +91,113 @@ pub struct Draggable {
     // If no hook is given, the entity will be pinned to the cursor
     // as it was when the drag was started.
     pub hook: Option<Vec2>,
-    pub groups: Vec<Group>,
+    pub groups: GroupMask,
     pub drop_strategy: DropStrategy,
     pub lock_y: bool,
+    /// The mouse buttons that start a drag, touches drag if it contains `MouseButton::Left`
+    pub buttons: Vec<MouseButton>,
 }
 
 impl Default for Draggable {
     fn default() -> Self {
         Self {
             hook: None,
-            groups: vec![Group::default()],
+            groups: Group::default().into(),
             drop_strategy: DropStrategy::Leave,
             lock_y: false,
+            buttons: vec![MouseButton::Left],
         }
     }
 }
 
+/// Starts dragging the draggables under the mouse cursor when one of their buttons is pressed
+/// and under every finger that touches the screen.
 pub fn mouse_press_start_drag_system(
     interaction_state: Res<InteractionState>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    draggables: Query<(Entity, &Draggable, &GlobalTransform), With<Interactable>>,
+    touches: Res<Touches>,
+    draggables: Query<(
+        Entity,
+        &Draggable,
+        &Interactable,
+        &GlobalTransform,
+        Has<Dragged>,
+    )>,
     mut commands: Commands,
 ) {
-    if !mouse_button_input.just_pressed(MouseButton::Left) {
+    let pressed: Vec<(PointerId, MouseButton)> = mouse_button_input
+        .get_just_pressed()
+        .map(|button| (PointerId::Mouse, *button))
+        .chain(
+            touches
+                .iter_just_pressed()
+                .map(|touch| (PointerId::Touch(touch.id()), MouseButton::Left)),
+        )
+        .collect();
+    if pressed.is_empty() {
         return;
     }
-    for (entity, draggable, global_transform) in draggables.iter() {
-        for group in draggable.groups.iter() {
-            if let Some(list) = interaction_state.ordered_interact_list_map.get(group) {
+    for (entity, draggable, interactable, global_transform, is_dragged) in draggables.iter() {
+        // An entity is only dragged by one pointer at a time
+        if is_dragged || !interactable.enabled {
+            continue;
+        }
+        'pointers: for (pointer, button) in pressed.iter() {
+            if !draggable.buttons.contains(button) {
+                continue;
+            }
+            for group in draggable.groups.iter() {
+                let list = interaction_state.get_pointer_group(*pointer, group);
                 if let Some((_, position)) = list.iter().find(|(e, _)| e == &entity) {
                     let translation = draggable
                         .hook
                         .unwrap_or(global_transform.translation().truncate() - *position);
                     commands.entity(entity).insert(Dragged {
-                        group: *group,
+                        group,
+                        pointer: *pointer,
+                        button: *button,
                         translation,
                         origin: global_transform.translation().truncate(),
                         just_dropped: false,
                         just_dragged: true,
                     });
-                    break;
+                    break 'pointers;
                 }
             }
         }
     }
 }
 
+/// Drops the dragged entities when the pointer dragging them is released
+/// or their interactable is disabled.
+/// The `DropStrategy` applies unless the entity was dropped onto a `DropZone`.
 pub fn mouse_release_stop_drag_system(
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut draggables: Query<(Entity, &Draggable, &mut Dragged, &mut Transform), With<Interactable>>,
+    touches: Res<Touches>,
+    drop_zones: Res<DropZoneState>,
+    mut draggables: Query<(
+        Entity,
+        &Draggable,
+        &Interactable,
+        &mut Dragged,
+        &mut Transform,
+    )>,
     mut commands: Commands,
 ) {
-    if !mouse_button_input.just_released(MouseButton::Left) {
-        return;
-    }
-    for (entity, draggable, mut dragged, mut transform) in draggables.iter_mut() {
+    for (entity, draggable, interactable, mut dragged, mut transform) in draggables.iter_mut() {
+        let released = match dragged.pointer {
+            PointerId::Mouse => mouse_button_input.just_released(dragged.button),
+            PointerId::Touch(id) => touches.just_released(id) || touches.just_canceled(id),
+        };
+        if !released && interactable.enabled {
+            continue;
+        }
         if !dragged.just_dragged {
             dragged.just_dropped = true;
         }
-        if let DropStrategy::Reset = draggable.drop_strategy {
+        // An entity dropped onto a drop zone is left for the zone to handle
+        let rejected = !drop_zones.is_dropped(entity);
+        if rejected && matches!(draggable.drop_strategy, DropStrategy::Reset) {
             transform.translation = dragged.origin.extend(transform.translation.z);
         }
         commands.entity(entity).remove::<Dragged>();
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use super::layers::GroupMask;
+use super::shape::InteractionShape;
+use crate::{world_to_local, Dragged, Group, InteractionState, PointerId};
+
+/// Decides whether a drop zone accepts a dragged entity, given the dragged entity
+pub type DropPredicate = Arc<dyn Fn(EntityRef) -> bool + Send + Sync>;
+
+/// How a drop zone decides that a dragged entity is over it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropDetection {
+    /// The world position of the dragged entity is inside the zone's shape
+    #[default]
+    Entity,
+    /// The pointer dragging the entity is inside the zone's shape,
+    /// even if the entity can't follow it, like with `Draggable::lock_y`
+    Pointer,
+}
+
+/// An area dragged entities can be dropped onto. A dragged entity is over the zone as decided
+/// by its `DropDetection`, only the topmost zone accepting it counts.
+/// Dropping onto a zone sends `Dropped` and leaves the entity where it was dropped,
+/// otherwise the `DropStrategy` of the draggable applies.
+#[derive(Component, Clone)]
+pub struct DropZone {
+    /// The area of the zone in the local space of its entity
+    pub shape: InteractionShape,
+    /// The groups of the drags the zone accepts
+    pub groups: GroupMask,
+    /// Accepts only the dragged entities it returns `true` for, if given
+    pub predicate: Option<DropPredicate>,
+    pub detection: DropDetection,
+}
+
+impl DropZone {
+    /// A drop zone accepting the drags of the default group
+    pub fn new(shape: InteractionShape) -> Self {
+        Self {
+            shape,
+            groups: Group::default().into(),
+            predicate: None,
+            detection: DropDetection::Entity,
+        }
+    }
+
+    pub fn with_groups(mut self, groups: impl Into<GroupMask>) -> Self {
+        self.groups = groups.into();
+        self
+    }
+
+    pub fn with_detection(mut self, detection: DropDetection) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(EntityRef) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Whether the zone accepts `dragged`, being dragged in `group`
+    pub fn accepts(&self, group: Group, dragged: EntityRef) -> bool {
+        self.groups.contains(group)
+            && self
+                .predicate
+                .as_ref()
+                .is_none_or(|predicate| predicate(dragged))
+    }
+}
+
+/// A dragged entity moved over a drop zone accepting it
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct DragEnter {
+    pub dragged: Entity,
+    pub zone: Entity,
+}
+
+/// A dragged entity is over a drop zone accepting it, sent every frame
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct DragOver {
+    pub dragged: Entity,
+    pub zone: Entity,
+    /// The world position tested against the zone, see `DropDetection`
+    pub position: Vec2,
+}
+
+/// A dragged entity moved off a drop zone or stopped being dragged without a drop
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct DragLeave {
+    pub dragged: Entity,
+    pub zone: Entity,
+}
+
+/// A dragged entity was released over a drop zone accepting it
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct Dropped {
+    pub dragged: Entity,
+    pub zone: Entity,
+    /// The world position tested against the zone, see `DropDetection`
+    pub position: Vec2,
+}
+
+/// The drop zones the dragged entities are over
+#[derive(Resource, Default)]
+pub struct DropZoneState {
+    over: HashMap<Entity, Entity>,
+    dropped: HashMap<Entity, Entity>,
+}
+
+impl DropZoneState {
+    /// The drop zone `dragged` is over
+    pub fn zone(&self, dragged: Entity) -> Option<Entity> {
+        self.over.get(&dragged).copied()
+    }
+
+    /// Whether `dragged` was dropped onto a drop zone this frame, the drag plugin
+    /// then skips its `DropStrategy`
+    pub fn is_dropped(&self, dragged: Entity) -> bool {
+        self.dropped.contains_key(&dragged)
+    }
+}
+
+/// This system tracks which drop zone every dragged entity is over and sends the drag events.
+/// It runs before the drags are released, so a drop onto a zone replaces the `DropStrategy`.
+#[allow(clippy::too_many_arguments)]
+pub fn drop_zone_system(
+    interaction_state: Res<InteractionState>,
+    mut state: ResMut<DropZoneState>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    dragged: Query<(Entity, &Dragged, &GlobalTransform)>,
+    zones: Query<(Entity, &DropZone, &GlobalTransform)>,
+    entities: Query<EntityRef>,
+    mut drag_enter: EventWriter<DragEnter>,
+    mut drag_over: EventWriter<DragOver>,
+    mut drag_leave: EventWriter<DragLeave>,
+    mut dropped: EventWriter<Dropped>,
+) {
+    let state = &mut *state;
+    state.dropped.clear();
+
+    let mut over = HashMap::new();
+    for (entity, drag, dragged_transform) in dragged.iter() {
+        let pointer_position = interaction_state.pointer_position(drag.pointer, drag.group);
+        let entity_position = dragged_transform.translation().truncate();
+        let Ok(dragged_ref) = entities.get(entity) else {
+            continue;
+        };
+        let zone = zones
+            .iter()
+            .filter_map(|(zone, drop_zone, global_transform)| {
+                let position = match drop_zone.detection {
+                    DropDetection::Entity => entity_position,
+                    DropDetection::Pointer => pointer_position?,
+                };
+                let is_over = zone != entity
+                    && drop_zone
+                        .shape
+                        .contains(world_to_local(global_transform, position))
+                    && drop_zone.accepts(drag.group, dragged_ref);
+                is_over.then_some((zone, global_transform.translation().z, position))
+            })
+            .max_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+        let Some((zone, _, position)) = zone else {
+            continue;
+        };
+        over.insert(entity, zone);
+        if state.over.get(&entity) != Some(&zone) {
+            drag_enter.send(DragEnter {
+                dragged: entity,
+                zone,
+            });
+        }
+        drag_over.send(DragOver {
+            dragged: entity,
+            zone,
+            position,
+        });
+
+        let released = match drag.pointer {
+            PointerId::Mouse => mouse_button_input.just_released(drag.button),
+            PointerId::Touch(id) => touches.just_released(id) || touches.just_canceled(id),
+        };
+        if released {
+            over.remove(&entity);
+            state.dropped.insert(entity, zone);
+            dropped.send(Dropped {
+                dragged: entity,
+                zone,
+                position,
+            });
+        }
+    }
+
+    for (entity, zone) in state.over.iter() {
+        if over.get(entity) != Some(zone) && state.dropped.get(entity) != Some(zone) {
+            drag_leave.send(DragLeave {
+                dragged: *entity,
+                zone: *zone,
+            });
+        }
+    }
+    state.over = over;
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use super::layers::InteractionLayers;
+use crate::Group;
+
+/// A misconfiguration of the interaction setup.
+/// Each error is reported once, as an event and a warning in the log.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionError {
+    /// An `InteractionSource` was added to an entity without a `Camera`.
+    /// The source is ignored.
+    MissingCamera { source: Entity },
+    /// Two sources of a group render to the same window with the same camera order
+    /// and overlapping viewports, so it is undefined which one receives the cursor.
+    AmbiguousSources {
+        group: Group,
+        first: Entity,
+        second: Entity,
+    },
+}
+
+impl InteractionError {
+    /// The error message with the groups described by their layer names
+    pub fn describe(&self, layers: &InteractionLayers) -> String {
+        match self {
+            Self::MissingCamera { source } => {
+                format!("Interacting without camera not supported, source {source} is ignored.")
+            }
+            Self::AmbiguousSources {
+                group,
+                first,
+                second,
+            } => format!(
+                "Interaction sources {first} and {second} of group {} overlap with the same camera order.",
+                layers.label(*group)
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for InteractionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.describe(&InteractionLayers::default()))
+    }
+}
+
+impl std::error::Error for InteractionError {}
+
+/// What happens when an `InteractionError` is found
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionErrorPolicy {
+    /// Panic with the error message, the default in debug builds
+    Panic,
+    /// Log a warning, send the error event and keep going with the valid sources,
+    /// the default in release builds
+    Degrade,
+}
+
+impl Default for InteractionErrorPolicy {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Panic
+        } else {
+            Self::Degrade
+        }
+    }
+}
+
+/// The errors that have already been reported
+#[derive(Resource, Default)]
+pub struct ReportedInteractionErrors(pub HashSet<InteractionError>);
+
+pub fn report_interaction_error(
+    error: InteractionError,
+    policy: InteractionErrorPolicy,
+    layers: &InteractionLayers,
+    reported: &mut ReportedInteractionErrors,
+    errors: &mut EventWriter<InteractionError>,
+) {
+    if !reported.0.insert(error) {
+        return;
+    }
+    let message = error.describe(layers);
+    if policy == InteractionErrorPolicy::Panic {
+        panic!("{message}");
+    }
+    warn!("{message}");
+    errors.send(error);
+}
@@ -0,0 +1,537 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+use crate::{Group, Interactable, InteractionState, PointerId};
+
+macro_rules! bubbling_event {
+    ($($event:ty),*) => {
+        $(
+            impl Event for $event {
+                type Traversal = &'static Parent;
+                const AUTO_PROPAGATE: bool = true;
+            }
+
+            impl PointerEvent for $event {
+                fn entity(&self) -> Entity {
+                    self.entity
+                }
+
+                fn group(&self) -> Group {
+                    self.group
+                }
+            }
+        )*
+    };
+}
+
+// Pointer events are sent as buffered events and triggered on their entity as well.
+// Triggered events bubble up the `Parent` hierarchy, so an ancestor observing them
+// handles the events of its children: `Trigger::entity` is the observing ancestor while
+// the `entity` field stays the child that was hit. Calling `Trigger::propagate(false)`
+// stops the event from reaching further ancestors. When a child and its ancestor are hit
+// together, the event of the child bubbles to the ancestor in place of its own.
+bubbling_event!(
+    HoverEnter,
+    HoverExit,
+    Pressed,
+    Released,
+    Clicked,
+    DoubleClicked,
+    LongPressed,
+    Scrolled
+);
+
+/// A pointer started hovering an interactable entity
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HoverEnter {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// A pointer stopped hovering an interactable entity
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HoverExit {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    /// The world position of the pointer, `None` if it left the interaction sources
+    pub position: Option<Vec2>,
+}
+
+/// A button was pressed while a pointer hovered an interactable entity.
+/// Only the buttons in `Interactable::buttons` are reported, touches press `MouseButton::Left`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Pressed {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// A button was released while a pointer hovered an interactable entity
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Released {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// A button was pressed and released on the same interactable entity
+/// without turning into a long press
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Clicked {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// A second click on the same interactable entity within `PointerEventSettings::double_click_time`.
+/// It is sent in addition to the second `Clicked`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct DoubleClicked {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// A button was held on an interactable entity for `PointerEventSettings::long_press_time`
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct LongPressed {
+    pub entity: Entity,
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+/// The mouse wheel was scrolled while the mouse cursor hovered an interactable entity.
+/// Only the topmost hovered entity of each group receives it.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Scrolled {
+    pub entity: Entity,
+    pub group: Group,
+    /// The scrolled lines, positive y scrolls up and positive x to the right
+    pub delta: Vec2,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct PointerEventSettings {
+    /// The maximum time in seconds between two clicks of a double click
+    pub double_click_time: f32,
+    /// The time in seconds a button has to be held for a long press
+    pub long_press_time: f32,
+    /// How many pixels of a touchpad scroll make up one line of `Scrolled::delta`
+    pub scroll_pixels_per_line: f32,
+}
+
+impl Default for PointerEventSettings {
+    fn default() -> Self {
+        Self {
+            double_click_time: 0.3,
+            long_press_time: 0.5,
+            scroll_pixels_per_line: 20.0,
+        }
+    }
+}
+
+struct Press {
+    entity: Entity,
+    group: Group,
+    start: f32,
+    long_pressed: bool,
+}
+
+/// The last click of a pointer button, with every entity and group it hit
+struct Click {
+    time: f32,
+    hits: Vec<(Entity, Group)>,
+}
+
+/// What the pointer event system remembers between frames
+#[derive(Resource, Default)]
+pub struct PointerEventState {
+    hovered: HashSet<(PointerId, Group, Entity)>,
+    presses: HashMap<(PointerId, MouseButton), Vec<Press>>,
+    last_clicks: HashMap<(PointerId, MouseButton), Click>,
+}
+
+impl PointerEventState {
+    /// Whether `entity` is held down in `group` by `button` of `pointer`
+    pub fn is_pressed(
+        &self,
+        entity: Entity,
+        group: Group,
+        pointer: PointerId,
+        button: MouseButton,
+    ) -> bool {
+        self.presses.get(&(pointer, button)).is_some_and(|presses| {
+            presses
+                .iter()
+                .any(|press| press.entity == entity && press.group == group)
+        })
+    }
+
+    /// The group, pointer and button of every press held down on `entity`
+    pub fn presses_of(
+        &self,
+        entity: Entity,
+    ) -> impl Iterator<Item = (Group, PointerId, MouseButton)> + '_ {
+        self.presses
+            .iter()
+            .flat_map(move |(&(pointer, button), presses)| {
+                presses
+                    .iter()
+                    .filter(move |press| press.entity == entity)
+                    .map(move |press| (press.group, pointer, button))
+            })
+    }
+}
+
+/// The writers of the pointer events, with the commands triggering them for the observers
+/// and the parents they bubble up to
+#[derive(SystemParam)]
+pub struct PointerEventWriters<'w, 's> {
+    commands: Commands<'w, 's>,
+    parents: Query<'w, 's, &'static Parent>,
+    hover_enter: EventWriter<'w, HoverEnter>,
+    hover_exit: EventWriter<'w, HoverExit>,
+    pressed: EventWriter<'w, Pressed>,
+    released: EventWriter<'w, Released>,
+    clicked: EventWriter<'w, Clicked>,
+    double_clicked: EventWriter<'w, DoubleClicked>,
+    long_pressed: EventWriter<'w, LongPressed>,
+    scrolled: EventWriter<'w, Scrolled>,
+}
+
+/// This system turns the hits of the `InteractionState` and the button input into pointer events
+#[allow(clippy::too_many_arguments)]
+pub fn pointer_event_system(
+    interaction_state: Res<InteractionState>,
+    mut event_state: ResMut<PointerEventState>,
+    settings: Res<PointerEventSettings>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    time: Res<Time>,
+    interactables: Query<&Interactable>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut events: PointerEventWriters,
+) {
+    let now = time.elapsed_secs();
+    let event_state = &mut *event_state;
+    let PointerEventWriters {
+        commands,
+        parents,
+        hover_enter,
+        hover_exit,
+        pressed,
+        released,
+        clicked,
+        double_clicked,
+        long_pressed,
+        scrolled,
+    } = &mut events;
+
+    // hover
+    let mut hovered = HashSet::new();
+    for (&(pointer, group), list) in interaction_state.pointer_interact_list_map.iter() {
+        let mut entered = vec![];
+        for (entity, position) in list.iter() {
+            hovered.insert((pointer, group, *entity));
+            if !event_state.hovered.contains(&(pointer, group, *entity)) {
+                entered.push(HoverEnter {
+                    entity: *entity,
+                    group,
+                    pointer,
+                    position: *position,
+                    local_position: local_position(&interaction_state, pointer, group, *entity),
+                });
+            }
+        }
+        send(hover_enter, commands, parents, entered);
+    }
+    let mut exited: HashMap<PointerId, Vec<HoverExit>> = HashMap::new();
+    for &(pointer, group, entity) in event_state.hovered.difference(&hovered) {
+        exited.entry(pointer).or_default().push(HoverExit {
+            entity,
+            group,
+            pointer,
+            position: interaction_state.pointer_position(pointer, group),
+        });
+    }
+    for (_, events) in exited {
+        send(hover_exit, commands, parents, events);
+    }
+    event_state.hovered = hovered;
+
+    // buttons
+    let mut just_pressed: Vec<(PointerId, MouseButton)> = mouse_button_input
+        .get_just_pressed()
+        .map(|button| (PointerId::Mouse, *button))
+        .collect();
+    just_pressed.extend(
+        touches
+            .iter_just_pressed()
+            .map(|touch| (PointerId::Touch(touch.id()), MouseButton::Left)),
+    );
+    let mut just_released: Vec<(PointerId, MouseButton)> = mouse_button_input
+        .get_just_released()
+        .map(|button| (PointerId::Mouse, *button))
+        .collect();
+    just_released.extend(
+        touches
+            .iter_just_released()
+            .chain(touches.iter_just_canceled())
+            .map(|touch| (PointerId::Touch(touch.id()), MouseButton::Left)),
+    );
+
+    let accepts = |entity: Entity, button: MouseButton| {
+        interactables
+            .get(entity)
+            .is_ok_and(|interactable| interactable.buttons.contains(&button))
+    };
+
+    for &(pointer, button) in just_pressed.iter() {
+        let mut presses = vec![];
+        let mut events = vec![];
+        for_each_hit(
+            &interaction_state,
+            pointer,
+            |group, entity, position, local_position| {
+                if !accepts(entity, button) {
+                    return;
+                }
+                events.push(Pressed {
+                    entity,
+                    group,
+                    pointer,
+                    button,
+                    position,
+                    local_position,
+                });
+                presses.push(Press {
+                    entity,
+                    group,
+                    start: now,
+                    long_pressed: false,
+                });
+            },
+        );
+        send(pressed, commands, parents, events);
+        event_state.presses.insert((pointer, button), presses);
+    }
+
+    for (&(pointer, button), presses) in event_state.presses.iter_mut() {
+        let mut events = vec![];
+        for press in presses.iter_mut() {
+            if press.long_pressed || now - press.start < settings.long_press_time {
+                continue;
+            }
+            let list = interaction_state.get_pointer_group(pointer, press.group);
+            // The pointer has to stay on the entity for a long press
+            let Some((_, position)) = list.iter().find(|(e, _)| *e == press.entity) else {
+                continue;
+            };
+            press.long_pressed = true;
+            events.push(LongPressed {
+                entity: press.entity,
+                group: press.group,
+                pointer,
+                button,
+                position: *position,
+                local_position: local_position(
+                    &interaction_state,
+                    pointer,
+                    press.group,
+                    press.entity,
+                ),
+            });
+        }
+        send(long_pressed, commands, parents, events);
+    }
+
+    for &(pointer, button) in just_released.iter() {
+        let presses = event_state
+            .presses
+            .remove(&(pointer, button))
+            .unwrap_or_default();
+        let last_click = event_state.last_clicks.get(&(pointer, button));
+        let mut clicks = vec![];
+        let mut released_events = vec![];
+        let mut clicked_events = vec![];
+        let mut double_clicked_events = vec![];
+        for_each_hit(
+            &interaction_state,
+            pointer,
+            |group, entity, position, local_position| {
+                if !accepts(entity, button) {
+                    return;
+                }
+                released_events.push(Released {
+                    entity,
+                    group,
+                    pointer,
+                    button,
+                    position,
+                    local_position,
+                });
+                let is_click = presses.iter().any(|press| {
+                    press.entity == entity && press.group == group && !press.long_pressed
+                });
+                if !is_click {
+                    return;
+                }
+                clicked_events.push(Clicked {
+                    entity,
+                    group,
+                    pointer,
+                    button,
+                    position,
+                    local_position,
+                });
+                // Every entity and group hit by both clicks is double clicked
+                if last_click.is_some_and(|click| {
+                    now - click.time <= settings.double_click_time
+                        && click.hits.contains(&(entity, group))
+                }) {
+                    double_clicked_events.push(DoubleClicked {
+                        entity,
+                        group,
+                        pointer,
+                        button,
+                        position,
+                        local_position,
+                    });
+                } else {
+                    clicks.push((entity, group));
+                }
+            },
+        );
+        let double_clicked_any = !double_clicked_events.is_empty();
+        send(released, commands, parents, released_events);
+        send(clicked, commands, parents, clicked_events);
+        send(double_clicked, commands, parents, double_clicked_events);
+        // The clicks completing a double click don't start the next one
+        if !clicks.is_empty() {
+            event_state.last_clicks.insert(
+                (pointer, button),
+                Click {
+                    time: now,
+                    hits: clicks,
+                },
+            );
+        } else if double_clicked_any {
+            event_state.last_clicks.remove(&(pointer, button));
+        }
+    }
+
+    // scroll
+    let delta: Vec2 = mouse_wheel
+        .read()
+        .map(|evt| match evt.unit {
+            MouseScrollUnit::Line => Vec2::new(evt.x, evt.y),
+            MouseScrollUnit::Pixel => Vec2::new(evt.x, evt.y) / settings.scroll_pixels_per_line,
+        })
+        .sum();
+    if delta != Vec2::ZERO {
+        for (&(pointer, group), list) in interaction_state.pointer_interact_list_map.iter() {
+            let Some((entity, position)) = list.first() else {
+                continue;
+            };
+            if pointer != PointerId::Mouse {
+                continue;
+            }
+            send(
+                scrolled,
+                commands,
+                parents,
+                vec![Scrolled {
+                    entity: *entity,
+                    group,
+                    delta,
+                    position: *position,
+                    local_position: local_position(&interaction_state, pointer, group, *entity),
+                }],
+            );
+        }
+    }
+}
+
+trait PointerEvent: Event + Copy {
+    fn entity(&self) -> Entity;
+    fn group(&self) -> Group;
+}
+
+/// Sends the `events` of one pointer to the event readers and triggers them on their entities
+/// for the observers. An event isn't triggered on an entity it already bubbles up to from a
+/// descendant hit in the same group, so each observer handles a hit once.
+fn send<E: PointerEvent>(
+    writer: &mut EventWriter<E>,
+    commands: &mut Commands,
+    parents: &Query<&Parent>,
+    events: Vec<E>,
+) {
+    for event in events.iter() {
+        writer.send(*event);
+        let bubbles_up = events.iter().any(|other| {
+            other.group() == event.group()
+                && parents
+                    .iter_ancestors(other.entity())
+                    .any(|ancestor| ancestor == event.entity())
+        });
+        if !bubbles_up {
+            commands.trigger_targets(*event, event.entity());
+        }
+    }
+}
+
+fn local_position(
+    interaction_state: &InteractionState,
+    pointer: PointerId,
+    group: Group,
+    entity: Entity,
+) -> Vec2 {
+    interaction_state
+        .local_pointer_position(pointer, group, entity)
+        .unwrap_or_default()
+}
+
+/// Calls `f` with the group, entity, world and local position of every hit of `pointer`
+fn for_each_hit(
+    interaction_state: &InteractionState,
+    pointer: PointerId,
+    mut f: impl FnMut(Group, Entity, Vec2, Vec2),
+) {
+    for (&(hit_pointer, group), list) in interaction_state.pointer_interact_list_map.iter() {
+        if hit_pointer != pointer {
+            continue;
+        }
+        for (entity, position) in list.iter() {
+            f(
+                group,
+                *entity,
+                *position,
+                local_position(interaction_state, pointer, group, *entity),
+            );
+        }
+    }
+}
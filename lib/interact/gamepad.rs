@@ -0,0 +1,141 @@
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// The gamepad cursor plugin adds a virtual cursor moved with a gamepad stick or D-pad.
+/// It sends the same cursor and mouse button events as a real mouse, so everything
+/// built on the `InteractionState` and the drag systems works with a controller.
+pub struct GamepadCursorPlugin;
+
+impl Plugin for GamepadCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadCursor>()
+            .add_systems(PreUpdate, gamepad_cursor_system.before(InputSystem))
+            .add_systems(Update, gamepad_cursor_indicator_system);
+    }
+}
+
+#[derive(Resource)]
+pub struct GamepadCursor {
+    /// The window the cursor moves in, the primary window if `None`
+    pub window: Option<Entity>,
+    /// The logical window position of the cursor, `None` until a gamepad moved it
+    pub position: Option<Vec2>,
+    /// Logical pixels per second at full stick deflection
+    pub speed: f32,
+    /// Stick deflections below this length are ignored
+    pub dead_zone: f32,
+    /// The gamepad button that presses the cursor
+    pub press_button: GamepadButton,
+    /// The mouse button pressed by `press_button`
+    pub mouse_button: MouseButton,
+}
+
+impl Default for GamepadCursor {
+    fn default() -> Self {
+        Self {
+            window: None,
+            position: None,
+            speed: 600.0,
+            dead_zone: 0.15,
+            press_button: GamepadButton::South,
+            mouse_button: MouseButton::Left,
+        }
+    }
+}
+
+/// Marks a UI node that follows the gamepad cursor.
+/// It is hidden until a gamepad moved the cursor.
+#[derive(Component, Default)]
+pub struct GamepadCursorIndicator;
+
+fn gamepad_cursor_system(
+    mut gamepad_cursor: ResMut<GamepadCursor>,
+    gamepads: Query<&Gamepad>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    time: Res<Time>,
+    mut cursor_moved: EventWriter<CursorMoved>,
+    mut mouse_button_input: EventWriter<MouseButtonInput>,
+) {
+    let Some(window_entity) = gamepad_cursor
+        .window
+        .or_else(|| primary_window.get_single().ok())
+    else {
+        return;
+    };
+    let Ok(window) = windows.get(window_entity) else {
+        return;
+    };
+
+    let mut direction = Vec2::ZERO;
+    let mut state = None;
+    for gamepad in gamepads.iter() {
+        let stick = gamepad.left_stick();
+        if stick.length() > gamepad_cursor.dead_zone {
+            direction += stick;
+        }
+        direction += gamepad.dpad();
+        if gamepad.just_pressed(gamepad_cursor.press_button) {
+            state = Some(ButtonState::Pressed);
+        } else if gamepad.just_released(gamepad_cursor.press_button) {
+            state = Some(ButtonState::Released);
+        }
+    }
+
+    let window_size = Vec2::new(window.width(), window.height());
+    if direction != Vec2::ZERO {
+        let old_position = gamepad_cursor.position.unwrap_or(window_size / 2.0);
+        // Window coordinates grow downwards
+        let delta = Vec2::new(direction.x, -direction.y).clamp_length_max(1.0)
+            * gamepad_cursor.speed
+            * time.delta_secs();
+        let position = (old_position + delta).clamp(Vec2::ZERO, window_size);
+        gamepad_cursor.position = Some(position);
+        cursor_moved.send(CursorMoved {
+            window: window_entity,
+            position,
+            delta: Some(position - old_position),
+        });
+    } else if state.is_some() && gamepad_cursor.position.is_none() {
+        // Pressing before moving presses at the center of the window
+        let position = window_size / 2.0;
+        gamepad_cursor.position = Some(position);
+        cursor_moved.send(CursorMoved {
+            window: window_entity,
+            position,
+            delta: None,
+        });
+    }
+
+    if let Some(state) = state {
+        mouse_button_input.send(MouseButtonInput {
+            button: gamepad_cursor.mouse_button,
+            state,
+            window: window_entity,
+        });
+    }
+}
+
+fn gamepad_cursor_indicator_system(
+    gamepad_cursor: Res<GamepadCursor>,
+    mut indicators: Query<
+        (&mut Node, &mut Visibility, &ComputedNode),
+        With<GamepadCursorIndicator>,
+    >,
+) {
+    if !gamepad_cursor.is_changed() {
+        return;
+    }
+    let Some(position) = gamepad_cursor.position else {
+        return;
+    };
+    for (mut node, mut visibility, computed_node) in indicators.iter_mut() {
+        // Center the indicator on the cursor
+        let half_size = computed_node.size() * computed_node.inverse_scale_factor() / 2.0;
+        node.left = Val::Px(position.x - half_size.x);
+        node.top = Val::Px(position.y - half_size.y);
+        *visibility = Visibility::Inherited;
+    }
+}
@@ -1,7 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use bevy::input::touch::TouchPhase;
 use bevy::prelude::*;
-use bevy::render::camera::Camera;
+use bevy::render::camera::{Camera, NormalizedRenderTarget};
+use bevy::window::PrimaryWindow;
+
+use super::alpha::{sprite_alpha_at, HitMode};
+use super::cursor::cursor_icon_system;
+use super::error::{
+    report_interaction_error, InteractionError, InteractionErrorPolicy, ReportedInteractionErrors,
+};
+use super::events::{
+    pointer_event_system, Clicked, DoubleClicked, HoverEnter, HoverExit, LongPressed,
+    PointerEventSettings, PointerEventState, Pressed, Released, Scrolled,
+};
+use super::layers::{GroupMask, InteractionLayers};
+use super::markers::marker_system;
+use super::scroll::scroll_behavior_system;
+use super::shape::InteractionShape;
+use super::spatial::{spatial_index_system, SpatialIndex};
+use super::ui::ui_blocking_system;
 
 /// The interaction plugin adds cursor interactions for entities
 /// with the Interactable component.
@@ -10,22 +28,163 @@ pub struct InteractionPlugin;
 impl Plugin for InteractionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<InteractionState>()
-            .add_systems(Update, (interaction_state_system, interaction_system));
+            .init_resource::<PointerEventState>()
+            .init_resource::<PointerEventSettings>()
+            .init_resource::<SpatialIndex>()
+            .init_resource::<InteractionLayers>()
+            .init_resource::<InteractionErrorPolicy>()
+            .init_resource::<ReportedInteractionErrors>()
+            .add_event::<InteractionError>()
+            .add_event::<HoverEnter>()
+            .add_event::<HoverExit>()
+            .add_event::<Pressed>()
+            .add_event::<Released>()
+            .add_event::<Clicked>()
+            .add_event::<DoubleClicked>()
+            .add_event::<LongPressed>()
+            .add_event::<Scrolled>()
+            .add_systems(
+                Update,
+                (
+                    interaction_state_system,
+                    ui_blocking_system,
+                    spatial_index_system,
+                    interaction_system,
+                    pointer_event_system,
+                    marker_system,
+                )
+                    .chain()
+                    .in_set(InteractionSet),
+            )
+            .add_systems(Update, scroll_behavior_system.after(InteractionSet))
+            .add_systems(PostUpdate, cursor_icon_system);
     }
 }
 
+/// The systems updating the `InteractionState` and sending pointer events.
+/// Systems reading them should run after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InteractionSet;
+
 /// Using groups it is easy to have systems only interact with
 /// draggables in a specific group.
 /// An example usecase would be separate groups for draggables and drop zones.
+/// Groups can be given a name with `register_interaction_layer`.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
+pub struct Group(pub u16);
+
+/// Decides which of the overlapping hits in a group are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PickPolicy {
+    /// Every hit entity is interacted with
+    #[default]
+    All,
+    /// An entity is only interacted with if no other entity of the group is hit above it
+    Topmost,
+}
+
+/// Identifies something that points at the world, like the mouse cursor or a finger
 #[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Default)]
-pub struct Group(pub u8);
+pub enum PointerId {
+    #[default]
+    Mouse,
+    /// A finger on a touch screen, identified by its touch id
+    Touch(u64),
+}
 
 #[derive(Default, Resource)]
 pub struct InteractionState {
+    /// The entities hit by the mouse cursor per group, sorted from the topmost
+    /// (highest global z) down. Entities at the same depth are ordered by their id.
     pub ordered_interact_list_map: HashMap<Group, Vec<(Entity, Vec2)>>,
+    /// The entities hit by every pointer per group, sorted like `ordered_interact_list_map`
+    pub pointer_interact_list_map: HashMap<(PointerId, Group), Vec<(Entity, Vec2)>>,
+    /// The pick policy per group, groups without an entry use `PickPolicy::All`
+    pub group_policies: HashMap<Group, PickPolicy>,
+    /// The pointer position in the local space of each hit entity
+    pub local_cursor_positions: HashMap<(PointerId, Group, Entity), Vec2>,
+    /// The world position of the mouse cursor per group
     pub cursor_positions: HashMap<Group, Vec2>,
-    pub last_window_id: u32,
+    /// The world position of every pointer per group
+    pub pointer_positions: HashMap<(PointerId, Group), Vec2>,
+    /// The cameras each group is viewed through, sorted by descending camera order
+    pub source_views: HashMap<Group, Vec<SourceView>>,
+    /// The camera the cursor is currently routed through for each group
+    pub active_sources: HashMap<Group, Entity>,
+    /// The logical cursor position in each window the cursor is currently over
+    pub window_cursor_positions: HashMap<Entity, Vec2>,
+    /// The window and logical position of each finger on a touch screen.
+    /// Lifted fingers are kept until the next frame so their release can be handled.
+    pub window_touch_positions: HashMap<u64, (Entity, Vec2)>,
+    /// The pointers over a blocking UI node, they don't hit any interactable this frame
+    pub blocked_pointers: HashSet<PointerId>,
+    /// The window the cursor was moved in last
+    pub last_window: Option<Entity>,
     pub last_cursor_position: Vec2,
+    ended_touches: Vec<u64>,
+}
+
+/// A snapshot of the camera of an interaction source, used to convert
+/// between window and world coordinates
+#[derive(Clone)]
+pub struct SourceView {
+    /// The camera entity
+    pub entity: Entity,
+    pub camera: Camera,
+    pub transform: GlobalTransform,
+    /// The window the camera renders to, `None` for image or texture targets
+    pub window: Option<Entity>,
+}
+
+impl SourceView {
+    /// Converts a logical window position (origin at the top left, y down) into a world
+    /// position, using the camera's projection and viewport.
+    pub fn screen_to_world(&self, screen_position: Vec2) -> Option<Vec2> {
+        let viewport_position = screen_position - self.viewport_origin();
+        self.camera
+            .viewport_to_world_2d(&self.transform, viewport_position)
+            .ok()
+    }
+
+    /// Converts a world position into a logical window position
+    pub fn world_to_screen(&self, world_position: Vec2) -> Option<Vec2> {
+        self.camera
+            .world_to_viewport(&self.transform, world_position.extend(0.0))
+            .ok()
+            .map(|viewport_position| viewport_position + self.viewport_origin())
+    }
+
+    /// Whether a logical window position is inside the camera's viewport
+    pub fn viewport_contains(&self, screen_position: Vec2) -> bool {
+        self.camera
+            .logical_viewport_rect()
+            .is_some_and(|rect| rect.contains(screen_position))
+    }
+
+    /// Whether both views render to the same window with the same camera order
+    /// and overlapping viewports
+    fn is_ambiguous_with(&self, other: &SourceView) -> bool {
+        if self.window.is_none()
+            || self.window != other.window
+            || self.camera.order != other.camera.order
+        {
+            return false;
+        }
+        match (
+            self.camera.logical_viewport_rect(),
+            other.camera.logical_viewport_rect(),
+        ) {
+            (Some(rect), Some(other_rect)) => !rect.intersect(other_rect).is_empty(),
+            _ => false,
+        }
+    }
+
+    fn viewport_origin(&self) -> Vec2 {
+        self.camera
+            .logical_viewport_rect()
+            .map(|rect| rect.min)
+            .unwrap_or_default()
+    }
 }
 
 impl InteractionState {
@@ -35,136 +194,376 @@ impl InteractionState {
             None => vec![],
         }
     }
+
+    /// Converts a logical position in `window` into a world position as seen by the
+    /// topmost interaction source of `group` rendering to that window
+    /// whose viewport contains the position
+    pub fn screen_to_world(
+        &self,
+        group: Group,
+        window: Entity,
+        screen_position: Vec2,
+    ) -> Option<Vec2> {
+        self.source_views
+            .get(&group)?
+            .iter()
+            .find(|view| view.window == Some(window) && view.viewport_contains(screen_position))?
+            .screen_to_world(screen_position)
+    }
+
+    /// Converts a world position into a logical window position as seen by the
+    /// interaction source of `group` the cursor is routed through, or its
+    /// topmost source if the cursor isn't over any of them
+    pub fn world_to_screen(&self, group: Group, world_position: Vec2) -> Option<Vec2> {
+        let views = self.source_views.get(&group)?;
+        let active_source = self.active_sources.get(&group);
+        views
+            .iter()
+            .find(|view| Some(&view.entity) == active_source)
+            .or(views.first())?
+            .world_to_screen(world_position)
+    }
+
+    pub fn set_group_policy(&mut self, group: Group, policy: PickPolicy) {
+        self.group_policies.insert(group, policy);
+    }
+
+    pub fn group_policy(&self, group: Group) -> PickPolicy {
+        self.group_policies.get(&group).copied().unwrap_or_default()
+    }
+
+    /// The topmost entity hit in `group`
+    pub fn topmost(&self, group: Group) -> Option<(Entity, Vec2)> {
+        self.ordered_interact_list_map
+            .get(&group)
+            .and_then(|list| list.first().copied())
+    }
+
+    /// The cursor position in the local space of `entity` if it is hit in `group`
+    pub fn local_cursor_position(&self, group: Group, entity: Entity) -> Option<Vec2> {
+        self.local_pointer_position(PointerId::Mouse, group, entity)
+    }
+
+    /// The position of `pointer` in the local space of `entity` if it is hit in `group`
+    pub fn local_pointer_position(
+        &self,
+        pointer: PointerId,
+        group: Group,
+        entity: Entity,
+    ) -> Option<Vec2> {
+        self.local_cursor_positions
+            .get(&(pointer, group, entity))
+            .copied()
+    }
+
+    /// The world position of `pointer` in `group`
+    pub fn pointer_position(&self, pointer: PointerId, group: Group) -> Option<Vec2> {
+        self.pointer_positions.get(&(pointer, group)).copied()
+    }
+
+    /// The entities hit by `pointer` in `group`, topmost first
+    pub fn get_pointer_group(&self, pointer: PointerId, group: Group) -> &[(Entity, Vec2)] {
+        self.pointer_interact_list_map
+            .get(&(pointer, group))
+            .map_or(&[], |list| list.as_slice())
+    }
+
+    /// The active pointers, the mouse cursor first followed by the touches
+    pub fn pointers(&self) -> impl Iterator<Item = PointerId> + '_ {
+        let mouse = (!self.cursor_positions.is_empty()).then_some(PointerId::Mouse);
+        mouse.into_iter().chain(
+            self.window_touch_positions
+                .keys()
+                .map(|id| PointerId::Touch(*id)),
+        )
+    }
+}
+
+/// Transforms a world position into the local space of an entity,
+/// undoing its translation, rotation, scale and any skew from its parents.
+pub fn world_to_local(global_transform: &GlobalTransform, world_position: Vec2) -> Vec2 {
+    global_transform
+        .affine()
+        .inverse()
+        .transform_point3(world_position.extend(global_transform.translation().z))
+        .truncate()
 }
 
 /// Attach an interaction source to cameras you want to interact from
 #[derive(Component)]
 pub struct InteractionSource {
-    pub groups: Vec<Group>,
+    pub groups: GroupMask,
 }
 
 impl Default for InteractionSource {
     fn default() -> Self {
         Self {
-            groups: vec![Group::default()],
+            groups: Group::default().into(),
         }
     }
 }
 
-/// This system calculates the interaction point for each group
-/// whenever the cursor is moved.
+/// This system calculates the interaction point of every pointer for each group
+/// whenever the cursor or a finger is moved.
+#[allow(clippy::too_many_arguments)]
 fn interaction_state_system(
     mut interaction_state: ResMut<InteractionState>,
     mut cursor_moved: EventReader<CursorMoved>,
-    sources: Query<(&InteractionSource, &GlobalTransform, Option<&Camera>)>,
-    windows: Query<&Window>,
+    mut cursor_left: EventReader<CursorLeft>,
+    mut touch_input: EventReader<TouchInput>,
+    sources: Query<(
+        Entity,
+        &InteractionSource,
+        &GlobalTransform,
+        Option<&Camera>,
+    )>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    error_policy: Res<InteractionErrorPolicy>,
+    layers: Res<InteractionLayers>,
+    mut reported_errors: ResMut<ReportedInteractionErrors>,
+    mut error_events: EventWriter<InteractionError>,
 ) {
     interaction_state.cursor_positions.clear();
-    let window = windows.single();
+    interaction_state.pointer_positions.clear();
+    interaction_state.source_views.clear();
+    interaction_state.active_sources.clear();
 
-    for (interact_source, global_transform, camera) in sources.iter() {
-        for evt in cursor_moved.read() {
-            interaction_state.last_window_id = evt.window.index();
-            interaction_state.last_cursor_position = evt.position;
+    for evt in cursor_moved.read() {
+        interaction_state.last_window = Some(evt.window);
+        interaction_state.last_cursor_position = evt.position;
+        interaction_state
+            .window_cursor_positions
+            .insert(evt.window, evt.position);
+    }
+    for evt in cursor_left.read() {
+        interaction_state
+            .window_cursor_positions
+            .remove(&evt.window);
+    }
+
+    for id in std::mem::take(&mut interaction_state.ended_touches) {
+        interaction_state.window_touch_positions.remove(&id);
+    }
+    for evt in touch_input.read() {
+        interaction_state
+            .window_touch_positions
+            .insert(evt.id, (evt.window, evt.position));
+        if matches!(evt.phase, TouchPhase::Ended | TouchPhase::Canceled) {
+            interaction_state.ended_touches.push(evt.id);
         }
-        let projection_matrix = match camera {
-            Some(camera) => {
-                let viewport_size = camera
-                    .logical_viewport_size()
-                    .unwrap_or(Vec2::new(window.width(), window.height()));
-                // Create an orthographic projection matrix for 2D rendering
-                // Parameters define the view frustum:
-                // - Left/right bounds: centered on 0, extending to +/- half viewport width
-                // - Top/bottom bounds: centered on 0, extending to +/- half viewport height
-                // - Near/far planes: 0.0 to 1.0 for 2D
-                Mat4::orthographic_rh(
-                    -viewport_size.x / 2.0,
-                    viewport_size.x / 2.0,
-                    -viewport_size.y / 2.0,
-                    viewport_size.y / 2.0,
-                    0.0,
-                    1.0,
-                )
+    }
+    let primary_window = primary_window.get_single().ok();
+    let mut errors = vec![];
+
+    for (entity, interact_source, global_transform, camera) in sources.iter() {
+        let view = match camera {
+            Some(camera) if !camera.is_active => continue,
+            Some(camera) => SourceView {
+                entity,
+                camera: camera.clone(),
+                transform: *global_transform,
+                window: match camera.target.normalize(primary_window) {
+                    Some(NormalizedRenderTarget::Window(window_ref)) => Some(window_ref.entity()),
+                    _ => None,
+                },
+            },
+            None => {
+                errors.push(InteractionError::MissingCamera { source: entity });
+                continue;
             }
-            None => panic!("Interacting without camera not supported."),
         };
-        let window = windows.single();
-        let screen_size = Vec2::from([
-            window.width() as f32,
-            window.height() as f32,
-        ]);
-        let cursor_position = interaction_state.last_cursor_position;
-        let cursor_position_ndc = (cursor_position / screen_size) * 2.0 - Vec2::from([1.0, 1.0]);
-        let camera_matrix = global_transform.compute_matrix();
-        let ndc_to_world: Mat4 = camera_matrix * projection_matrix.inverse();
-        let cursor_position = ndc_to_world
-            .transform_point3(cursor_position_ndc.extend(1.0))
-            .truncate();
-
-        for group in &interact_source.groups {
-            if interaction_state
-                .cursor_positions
-                .insert(*group, cursor_position)
-                .is_some()
-            {
-                panic!(
-                    "Multiple interaction sources have been added to interaction group {:?}",
-                    group
-                );
+        for group in interact_source.groups.iter() {
+            interaction_state
+                .source_views
+                .entry(group)
+                .or_default()
+                .push(view.clone());
+        }
+    }
+
+    let InteractionState {
+        source_views,
+        window_cursor_positions,
+        window_touch_positions,
+        cursor_positions,
+        pointer_positions,
+        active_sources,
+        ..
+    } = &mut *interaction_state;
+    for (group, views) in source_views.iter_mut() {
+        views.sort_by_key(|view| std::cmp::Reverse(view.camera.order));
+        for (index, first) in views.iter().enumerate() {
+            for second in views[index + 1..].iter() {
+                if first.is_ambiguous_with(second) {
+                    errors.push(InteractionError::AmbiguousSources {
+                        group: *group,
+                        first: first.entity,
+                        second: second.entity,
+                    });
+                }
             }
         }
+        let routed = views.iter().find_map(|view| {
+            let cursor_position = window_cursor_positions.get(&view.window?)?;
+            Some((view.entity, route(view, *cursor_position)?))
+        });
+        if let Some((source, cursor_position)) = routed {
+            cursor_positions.insert(*group, cursor_position);
+            pointer_positions.insert((PointerId::Mouse, *group), cursor_position);
+            active_sources.insert(*group, source);
+        }
+        for (id, (window, touch_position)) in window_touch_positions.iter() {
+            let routed = views
+                .iter()
+                .filter(|view| view.window == Some(*window))
+                .find_map(|view| route(view, *touch_position));
+            if let Some(touch_position) = routed {
+                pointer_positions.insert((PointerId::Touch(*id), *group), touch_position);
+            }
+        }
+    }
+
+    for error in errors {
+        report_interaction_error(
+            error,
+            *error_policy,
+            &layers,
+            &mut reported_errors,
+            &mut error_events,
+        );
+    }
+}
+
+/// A pointer is routed to the topmost camera whose viewport contains it
+/// in the window the camera renders to
+fn route(view: &SourceView, screen_position: Vec2) -> Option<Vec2> {
+    if !view.viewport_contains(screen_position) {
+        return None;
     }
+    view.screen_to_world(screen_position)
 }
 
 /// This component makes an entity interactable with the mouse cursor
 #[derive(Component, Clone)]
 pub struct Interactable {
     /// The interaction groups this interactable entity belongs to
-    pub groups: Vec<Group>,
+    pub groups: GroupMask,
     /// The interaction area for the interactable entity
-    pub bounding_box: (Vec2, Vec2),
+    pub shape: InteractionShape,
+    /// How the cursor is tested against the interactable entity
+    pub hit_mode: HitMode,
+    /// Overrides the pick policy of the groups this entity is hit in
+    pub pick_policy: Option<PickPolicy>,
+    /// The mouse buttons that send pointer events for this entity,
+    /// touches count as `MouseButton::Left`
+    pub buttons: Vec<MouseButton>,
+    /// Disabled entities are never hit, but keep their configuration.
+    /// Disabling a dragged entity ends its drag with the `DropStrategy` of its draggable.
+    pub enabled: bool,
+    /// Pass-through entities are hit, hovered and dragged,
+    /// but don't hide the entities below them from the topmost pick policy
+    pub pass_through: bool,
 }
 
 impl Default for Interactable {
     fn default() -> Self {
         Self {
-            groups: vec![Group::default()],
-            bounding_box: (Vec2::default(), Vec2::default()),
+            groups: Group::default().into(),
+            shape: InteractionShape::default(),
+            hit_mode: HitMode::default(),
+            pick_policy: None,
+            buttons: vec![
+                MouseButton::Left,
+                MouseButton::Right,
+                MouseButton::Middle,
+            ],
+            enabled: true,
+            pass_through: false,
         }
     }
 }
 
+struct Hit {
+    depth: f32,
+    entity: Entity,
+    pointer_position: Vec2,
+    local_pointer_position: Vec2,
+    pick_policy: Option<PickPolicy>,
+    pass_through: bool,
+}
+
 /// This system checks what for what groups an entity is currently interacted with
 fn interaction_system(
     mut interaction_state: ResMut<InteractionState>,
-    interactables: Query<(Entity, &GlobalTransform, &Interactable)>,
+    spatial_index: Res<SpatialIndex>,
+    interactables: Query<(&GlobalTransform, &Interactable, Option<&Sprite>)>,
+    images: Option<Res<Assets<Image>>>,
+    atlas_layouts: Option<Res<Assets<TextureAtlasLayout>>>,
 ) {
     interaction_state.ordered_interact_list_map.clear();
+    interaction_state.pointer_interact_list_map.clear();
+    interaction_state.local_cursor_positions.clear();
 
-    for (entity, global_transform, interactable) in interactables.iter() {
-        let cursor_positions = interaction_state.cursor_positions.clone();
-        for (group, cursor_position) in cursor_positions {
-            if !interactable.groups.contains(&group) {
+    let mut hits: HashMap<(PointerId, Group), Vec<Hit>> = HashMap::new();
+    for (&(pointer, group), &pointer_position) in interaction_state.pointer_positions.iter() {
+        if interaction_state.blocked_pointers.contains(&pointer) {
+            continue;
+        }
+        for entity in spatial_index.query(group, pointer_position) {
+            let Ok((global_transform, interactable, sprite)) = interactables.get(entity) else {
+                continue;
+            };
+            if !interactable.enabled || !interactable.groups.contains(group) {
+                continue;
+            }
+            let local_pointer_position = world_to_local(global_transform, pointer_position);
+            if !interactable.shape.contains(local_pointer_position) {
                 continue;
             }
-            // TODO: use bounding_mesh
-            let relative_cursor_position = (cursor_position
-                - global_transform.translation().truncate())
-                / Transform::from(*global_transform).scale.truncate();
-            if (interactable.bounding_box.0.x..interactable.bounding_box.1.x)
-                .contains(&relative_cursor_position.x)
-                && (interactable.bounding_box.0.y..interactable.bounding_box.1.y)
-                    .contains(&relative_cursor_position.y)
+            if let (HitMode::Alpha { threshold }, Some(sprite), Some(images), Some(layouts)) =
+                (interactable.hit_mode, sprite, &images, &atlas_layouts)
             {
-                let interaction = (entity, cursor_position);
-                if let Some(list) = interaction_state.ordered_interact_list_map.get_mut(&group) {
-                    list.push(interaction)
-                } else {
-                    interaction_state
-                        .ordered_interact_list_map
-                        .insert(group, vec![interaction]);
+                // Images that can't be sampled fall back to the shape
+                let alpha = sprite_alpha_at(sprite, local_pointer_position, images, layouts);
+                if alpha.is_some_and(|alpha| alpha <= threshold) {
+                    continue;
                 }
             }
+            hits.entry((pointer, group)).or_default().push(Hit {
+                depth: global_transform.translation().z,
+                entity,
+                pointer_position,
+                local_pointer_position,
+                pick_policy: interactable.pick_policy,
+                pass_through: interactable.pass_through,
+            });
+        }
+    }
+
+    for ((pointer, group), mut group_hits) in hits {
+        group_hits.sort_by(|a, b| b.depth.total_cmp(&a.depth).then(a.entity.cmp(&b.entity)));
+        let group_policy = interaction_state.group_policy(group);
+        let mut list = Vec::with_capacity(group_hits.len());
+        let mut covered = false;
+        for hit in group_hits {
+            // Entities with the topmost policy are only kept if no other hit covers them,
+            // pass-through hits don't cover the ones below
+            if covered && hit.pick_policy.unwrap_or(group_policy) == PickPolicy::Topmost {
+                continue;
+            }
+            covered |= !hit.pass_through;
+            interaction_state
+                .local_cursor_positions
+                .insert((pointer, group, hit.entity), hit.local_pointer_position);
+            list.push((hit.entity, hit.pointer_position));
+        }
+        if pointer == PointerId::Mouse {
+            interaction_state
+                .ordered_interact_list_map
+                .insert(group, list.clone());
         }
+        interaction_state
+            .pointer_interact_list_map
+            .insert((pointer, group), list);
     }
 }
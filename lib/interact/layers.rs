@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::Group;
+
+/// A set of groups stored as a bitmask, so membership checks are a bit test
+/// and overlap checks a bitwise and.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct GroupMask {
+    bits: Vec<u64>,
+}
+
+impl GroupMask {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, group: Group) {
+        let (word, bit) = Self::position(group);
+        if self.bits.len() <= word {
+            self.bits.resize(word + 1, 0);
+        }
+        self.bits[word] |= bit;
+    }
+
+    pub fn remove(&mut self, group: Group) {
+        let (word, bit) = Self::position(group);
+        if let Some(bits) = self.bits.get_mut(word) {
+            *bits &= !bit;
+        }
+        // Equal sets have equal words, so masks compare and hash by their groups
+        while self.bits.last() == Some(&0) {
+            self.bits.pop();
+        }
+    }
+
+    pub fn contains(&self, group: Group) -> bool {
+        let (word, bit) = Self::position(group);
+        self.bits.get(word).is_some_and(|bits| bits & bit != 0)
+    }
+
+    /// Whether both masks have a group in common
+    pub fn intersects(&self, other: &GroupMask) -> bool {
+        self.bits
+            .iter()
+            .zip(other.bits.iter())
+            .any(|(bits, other_bits)| bits & other_bits != 0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|bits| *bits == 0)
+    }
+
+    /// The groups in the mask in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = Group> + '_ {
+        self.bits.iter().enumerate().flat_map(|(word, bits)| {
+            (0..64)
+                .filter(move |bit| bits & (1 << bit) != 0)
+                .map(move |bit| Group((word * 64 + bit) as u16))
+        })
+    }
+
+    fn position(group: Group) -> (usize, u64) {
+        let index = group.0 as usize;
+        (index / 64, 1 << (index % 64))
+    }
+}
+
+impl From<Group> for GroupMask {
+    fn from(group: Group) -> Self {
+        Self::from_iter([group])
+    }
+}
+
+impl From<Vec<Group>> for GroupMask {
+    fn from(groups: Vec<Group>) -> Self {
+        Self::from_iter(groups)
+    }
+}
+
+impl<const N: usize> From<[Group; N]> for GroupMask {
+    fn from(groups: [Group; N]) -> Self {
+        Self::from_iter(groups)
+    }
+}
+
+impl FromIterator<Group> for GroupMask {
+    fn from_iter<T: IntoIterator<Item = Group>>(groups: T) -> Self {
+        let mut mask = Self::new();
+        for group in groups {
+            mask.insert(group);
+        }
+        mask
+    }
+}
+
+/// The names of the registered interaction layers.
+/// Layers get their groups in registration order starting at `Group(0)`,
+/// so the first registered layer is `Group::default()`.
+#[derive(Resource, Debug, Default)]
+pub struct InteractionLayers {
+    names: Vec<String>,
+    groups: HashMap<String, Group>,
+}
+
+impl InteractionLayers {
+    /// Registers a layer and returns its group.
+    /// Registering a name twice returns the same group.
+    pub fn register(&mut self, name: impl Into<String>) -> Group {
+        let name = name.into();
+        if let Some(group) = self.groups.get(&name) {
+            return *group;
+        }
+        let index = u16::try_from(self.names.len()).expect("Too many interaction layers");
+        let group = Group(index);
+        self.names.push(name.clone());
+        self.groups.insert(name, group);
+        group
+    }
+
+    pub fn get(&self, name: &str) -> Option<Group> {
+        self.groups.get(name).copied()
+    }
+
+    pub fn name(&self, group: Group) -> Option<&str> {
+        self.names.get(group.0 as usize).map(String::as_str)
+    }
+
+    /// A description of the group for logs, like `"items" (Group(1))`
+    pub fn label(&self, group: Group) -> String {
+        match self.name(group) {
+            Some(name) => format!("{name:?} ({group:?})"),
+            None => format!("{group:?}"),
+        }
+    }
+}
+
+pub trait InteractionLayersAppExt {
+    /// Registers a named interaction layer, see `InteractionLayers::register`
+    fn register_interaction_layer(&mut self, name: impl Into<String>) -> &mut Self;
+}
+
+impl InteractionLayersAppExt for App {
+    fn register_interaction_layer(&mut self, name: impl Into<String>) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(InteractionLayers::default)
+            .register(name);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn masks_of_the_same_groups_are_equal() {
+        let mut mask = GroupMask::from([
+            Group(3),
+            Group(64),
+            Group(130),
+        ]);
+        mask.remove(Group(130));
+        mask.insert(Group(70));
+        mask.remove(Group(70));
+        let expected = GroupMask::from([Group(64), Group(3)]);
+        assert_eq!(mask, expected);
+        assert_eq!(
+            HashSet::from([mask.clone()]),
+            HashSet::from([expected.clone()])
+        );
+        assert_eq!(mask.iter().collect::<Vec<_>>(), [Group(3), Group(64)]);
+        assert!(mask.contains(Group(64)));
+        assert!(!mask.contains(Group(130)));
+    }
+
+    #[test]
+    fn remove_trims_the_empty_words() {
+        let mut mask = GroupMask::from([Group(1), Group(200)]);
+        assert_eq!(mask.bits.len(), 4);
+        mask.remove(Group(200));
+        assert_eq!(mask.bits.len(), 1);
+        mask.remove(Group(1));
+        assert!(mask.bits.is_empty());
+        assert!(mask.is_empty());
+        assert_eq!(mask, GroupMask::new());
+
+        // Removing a group beyond the last word keeps the mask as it is
+        let mut mask = GroupMask::from(Group(5));
+        mask.remove(Group(300));
+        assert_eq!(mask, GroupMask::from(Group(5)));
+    }
+
+    #[test]
+    fn intersects_across_words() {
+        let low = GroupMask::from([Group(0), Group(63)]);
+        let high = GroupMask::from([Group(64), Group(128)]);
+        let both = GroupMask::from([Group(63), Group(128)]);
+        assert!(!low.intersects(&high));
+        assert!(!high.intersects(&low));
+        assert!(low.intersects(&both));
+        assert!(high.intersects(&both));
+        assert!(both.intersects(&high));
+        assert!(!low.intersects(&GroupMask::new()));
+    }
+}
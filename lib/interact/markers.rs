@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::events::{PointerEventState, Pressed};
+use crate::{Group, InteractionState, PointerId};
+
+/// Added to interactable entities while a pointer hovers them.
+/// If several pointers or groups hit the entity, the mouse cursor and the lowest group win.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Hovered {
+    pub group: Group,
+    pub pointer: PointerId,
+    /// The pointer position in the local space of the entity
+    pub local_pos: Vec2,
+}
+
+/// Added to interactable entities while a button pressed on them is held down.
+/// If several buttons are held, it names the latest press until that one is released.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct PressedBy {
+    pub group: Group,
+    pub pointer: PointerId,
+    pub button: MouseButton,
+}
+
+/// This system keeps the `Hovered` and `PressedBy` components in sync with the interactions
+pub fn marker_system(
+    interaction_state: Res<InteractionState>,
+    event_state: Res<PointerEventState>,
+    mut pressed_events: EventReader<Pressed>,
+    mut hovered: Query<(Entity, &mut Hovered)>,
+    pressed: Query<(Entity, &PressedBy)>,
+    mut commands: Commands,
+) {
+    let mut hits: HashMap<Entity, Hovered> = HashMap::new();
+    for (&(pointer, group), list) in interaction_state.pointer_interact_list_map.iter() {
+        for (entity, _) in list.iter() {
+            let hit = Hovered {
+                group,
+                pointer,
+                local_pos: interaction_state
+                    .local_pointer_position(pointer, group, *entity)
+                    .unwrap_or_default(),
+            };
+            let wins = hits.get(entity).is_none_or(|other| {
+                (hit.pointer != PointerId::Mouse, hit.group.0)
+                    < (other.pointer != PointerId::Mouse, other.group.0)
+            });
+            if wins {
+                hits.insert(*entity, hit);
+            }
+        }
+    }
+
+    for (entity, mut current) in hovered.iter_mut() {
+        match hits.remove(&entity) {
+            Some(hit) => {
+                current.set_if_neq(hit);
+            }
+            None => {
+                commands.entity(entity).remove::<Hovered>();
+            }
+        }
+    }
+    for (entity, hit) in hits {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(hit);
+        }
+    }
+
+    // An entity stays pressed while any of the buttons pressed on it is held
+    for (entity, press) in pressed.iter() {
+        if event_state.is_pressed(entity, press.group, press.pointer, press.button) {
+            continue;
+        }
+        match event_state.presses_of(entity).next() {
+            Some((group, pointer, button)) => {
+                commands.entity(entity).insert(PressedBy {
+                    group,
+                    pointer,
+                    button,
+                });
+            }
+            None => {
+                commands.entity(entity).remove::<PressedBy>();
+            }
+        }
+    }
+    for evt in pressed_events.read() {
+        if let Some(mut entity_commands) = commands.get_entity(evt.entity) {
+            entity_commands.insert(PressedBy {
+                group: evt.group,
+                pointer: evt.pointer,
+                button: evt.button,
+            });
+        }
+    }
+}
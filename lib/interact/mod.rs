@@ -1,2 +1,20 @@
+pub mod alpha;
+pub mod cursor;
+pub mod debug;
 pub mod drag;
+pub mod drop;
+pub mod error;
+pub mod events;
+pub mod gamepad;
+#[allow(clippy::module_inception)]
 pub mod interact;
+pub mod layers;
+pub mod markers;
+pub mod replay;
+pub mod scroll;
+pub mod selection;
+pub mod shape;
+pub mod spatial;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod ui;
@@ -0,0 +1,391 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel};
+use bevy::input::touch::TouchPhase;
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+/// The input plugin records the cursor, mouse button, touch, mouse wheel and keyboard input
+/// of every frame into the `InputRecorder`.
+/// With a path the recording is written there as JSON when the app exits.
+#[derive(Default)]
+pub struct InputRecorderPlugin {
+    pub path: Option<PathBuf>,
+}
+
+impl Plugin for InputRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputRecorder {
+            recording: InputRecording::default(),
+            enabled: true,
+            path: self.path.clone(),
+            elapsed: 0.0,
+        })
+        .add_systems(PreUpdate, record_input_system.after(InputSystem))
+        .add_systems(Last, save_recording_system);
+    }
+}
+
+#[derive(Resource)]
+pub struct InputRecorder {
+    pub recording: InputRecording,
+    /// Frames are only recorded while enabled
+    pub enabled: bool,
+    /// Where the recording is saved when the app exits
+    pub path: Option<PathBuf>,
+    elapsed: f32,
+}
+
+/// The recorded input of a range of frames, serializable to JSON
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct InputRecording {
+    /// The real time duration of every recorded frame in seconds
+    pub frame_times: Vec<f32>,
+    /// The input of every frame, in the order the frames were recorded
+    pub inputs: Vec<RecordedInput>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RecordedInput {
+    /// The index of the frame in `InputRecording::frame_times`
+    pub frame: usize,
+    /// The seconds since the start of the recording
+    pub time: f32,
+    pub event: RecordedEvent,
+}
+
+/// A recorded input event. Positions are logical window positions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type")]
+pub enum RecordedEvent {
+    CursorMoved {
+        position: (f32, f32),
+    },
+    CursorLeft,
+    MouseButton {
+        button: RecordedButton,
+        pressed: bool,
+    },
+    Touch {
+        id: u64,
+        phase: RecordedTouchPhase,
+        position: (f32, f32),
+    },
+    MouseWheel {
+        x: f32,
+        y: f32,
+        /// Whether the delta is in pixels instead of lines
+        pixels: bool,
+    },
+    /// A key was pressed or released, key repeats are not recorded
+    Key {
+        key: KeyCode,
+        pressed: bool,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedTouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Canceled,
+}
+
+impl From<MouseButton> for RecordedButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+            MouseButton::Back => Self::Back,
+            MouseButton::Forward => Self::Forward,
+            MouseButton::Other(id) => Self::Other(id),
+        }
+    }
+}
+
+impl From<RecordedButton> for MouseButton {
+    fn from(button: RecordedButton) -> Self {
+        match button {
+            RecordedButton::Left => Self::Left,
+            RecordedButton::Right => Self::Right,
+            RecordedButton::Middle => Self::Middle,
+            RecordedButton::Back => Self::Back,
+            RecordedButton::Forward => Self::Forward,
+            RecordedButton::Other(id) => Self::Other(id),
+        }
+    }
+}
+
+impl From<TouchPhase> for RecordedTouchPhase {
+    fn from(phase: TouchPhase) -> Self {
+        match phase {
+            TouchPhase::Started => Self::Started,
+            TouchPhase::Moved => Self::Moved,
+            TouchPhase::Ended => Self::Ended,
+            TouchPhase::Canceled => Self::Canceled,
+        }
+    }
+}
+
+impl From<RecordedTouchPhase> for TouchPhase {
+    fn from(phase: RecordedTouchPhase) -> Self {
+        match phase {
+            RecordedTouchPhase::Started => Self::Started,
+            RecordedTouchPhase::Moved => Self::Moved,
+            RecordedTouchPhase::Ended => Self::Ended,
+            RecordedTouchPhase::Canceled => Self::Canceled,
+        }
+    }
+}
+
+impl InputRecording {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Number of recorded frames
+    pub fn len(&self) -> usize {
+        self.frame_times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frame_times.is_empty()
+    }
+
+    /// The input of a frame
+    pub fn frame(&self, frame: usize) -> impl Iterator<Item = &RecordedInput> + '_ {
+        self.inputs.iter().filter(move |input| input.frame == frame)
+    }
+}
+
+/// This system records the input events that reached the input resources this frame,
+/// including the synthetic ones of the gamepad cursor
+#[allow(clippy::too_many_arguments)]
+fn record_input_system(
+    mut recorder: ResMut<InputRecorder>,
+    time: Res<Time<Real>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut cursor_left: EventReader<CursorLeft>,
+    mut mouse_button_input: EventReader<MouseButtonInput>,
+    mut touch_input: EventReader<TouchInput>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut keyboard_input: EventReader<KeyboardInput>,
+) {
+    if !recorder.enabled {
+        cursor_moved.clear();
+        cursor_left.clear();
+        mouse_button_input.clear();
+        touch_input.clear();
+        mouse_wheel.clear();
+        keyboard_input.clear();
+        return;
+    }
+    recorder.elapsed += time.delta_secs();
+    let elapsed = recorder.elapsed;
+    let recording = &mut recorder.recording;
+    let frame = recording.frame_times.len();
+    recording.frame_times.push(time.delta_secs());
+
+    let mut events = vec![];
+    events.extend(cursor_moved.read().map(|evt| RecordedEvent::CursorMoved {
+        position: evt.position.into(),
+    }));
+    events.extend(cursor_left.read().map(|_| RecordedEvent::CursorLeft));
+    events.extend(
+        mouse_button_input
+            .read()
+            .map(|evt| RecordedEvent::MouseButton {
+                button: evt.button.into(),
+                pressed: evt.state.is_pressed(),
+            }),
+    );
+    events.extend(touch_input.read().map(|evt| RecordedEvent::Touch {
+        id: evt.id,
+        phase: evt.phase.into(),
+        position: evt.position.into(),
+    }));
+    events.extend(mouse_wheel.read().map(|evt| RecordedEvent::MouseWheel {
+        x: evt.x,
+        y: evt.y,
+        pixels: evt.unit == MouseScrollUnit::Pixel,
+    }));
+    events.extend(
+        keyboard_input
+            .read()
+            .filter(|evt| !evt.repeat)
+            .map(|evt| RecordedEvent::Key {
+                key: evt.key_code,
+                pressed: evt.state.is_pressed(),
+            }),
+    );
+    recording
+        .inputs
+        .extend(events.into_iter().map(|event| RecordedInput {
+            frame,
+            time: elapsed,
+            event,
+        }));
+}
+
+fn save_recording_system(recorder: Res<InputRecorder>, mut app_exit: EventReader<AppExit>) {
+    if app_exit.read().next().is_none() {
+        return;
+    }
+    let Some(path) = &recorder.path else {
+        return;
+    };
+    match recorder.recording.save(path) {
+        Ok(()) => info!("Input recording saved to {}", path.display()),
+        Err(err) => error!(
+            "Failed to save the input recording to {}: {err}",
+            path.display()
+        ),
+    }
+}
+
+/// Replays an `InputRecording` into an app, one recorded frame per update.
+/// Time advances by the recorded frame times, so timing based interactions
+/// like long presses and double clicks happen on the same frames as recorded.
+pub struct InputReplay {
+    recording: InputRecording,
+    frame: usize,
+}
+
+impl InputReplay {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            frame: 0,
+        }
+    }
+
+    /// The next frame to replay
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.len()
+    }
+
+    /// Sends the input of the next frame to the primary window of `app` and updates it.
+    /// Returns `false` once all frames have been replayed.
+    pub fn step(&mut self, app: &mut App) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        let world = app.world_mut();
+        let window = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(world);
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            self.recording.frame_times[self.frame],
+        )));
+        for input in self.recording.frame(self.frame) {
+            send_recorded_event(world, window, input.event);
+        }
+        app.update();
+        self.frame += 1;
+        true
+    }
+
+    /// Replays all remaining frames
+    pub fn run(&mut self, app: &mut App) {
+        while self.step(app) {}
+    }
+}
+
+fn send_recorded_event(world: &mut World, window: Entity, event: RecordedEvent) {
+    match event {
+        RecordedEvent::CursorMoved { position } => {
+            world.send_event(CursorMoved {
+                window,
+                position: position.into(),
+                delta: None,
+            });
+        }
+        RecordedEvent::CursorLeft => {
+            world.send_event(CursorLeft { window });
+        }
+        RecordedEvent::MouseButton { button, pressed } => {
+            world.send_event(MouseButtonInput {
+                button: button.into(),
+                state: button_state(pressed),
+                window,
+            });
+        }
+        RecordedEvent::Touch {
+            id,
+            phase,
+            position,
+        } => {
+            world.send_event(TouchInput {
+                phase: phase.into(),
+                position: position.into(),
+                window,
+                force: None,
+                id,
+            });
+        }
+        RecordedEvent::MouseWheel { x, y, pixels } => {
+            world.send_event(MouseWheel {
+                unit: if pixels {
+                    MouseScrollUnit::Pixel
+                } else {
+                    MouseScrollUnit::Line
+                },
+                x,
+                y,
+                window,
+            });
+        }
+        RecordedEvent::Key { key, pressed } => {
+            world.send_event(KeyboardInput {
+                key_code: key,
+                // Only the key codes are recorded, not the keyboard layout
+                logical_key: Key::Unidentified(NativeKey::Unidentified),
+                state: button_state(pressed),
+                repeat: false,
+                window,
+            });
+        }
+    }
+}
+
+fn button_state(pressed: bool) -> ButtonState {
+    if pressed {
+        ButtonState::Pressed
+    } else {
+        ButtonState::Released
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use super::events::Scrolled;
+
+/// A built-in reaction of an interactable entity to `Scrolled` events
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum ScrollBehavior {
+    /// Scales the entity uniformly by `1.0 + speed` per line scrolled up,
+    /// keeping the scale within `min` and `max`
+    Scale { min: f32, max: f32, speed: f32 },
+    /// Rotates the entity counterclockwise by `speed` radians per line scrolled up
+    Rotate { speed: f32 },
+}
+
+impl ScrollBehavior {
+    pub fn scale(min: f32, max: f32) -> Self {
+        Self::Scale {
+            min,
+            max,
+            speed: 0.1,
+        }
+    }
+
+    pub fn rotate() -> Self {
+        Self::Rotate {
+            speed: std::f32::consts::PI / 12.0,
+        }
+    }
+}
+
+/// This system applies the `ScrollBehavior` of scrolled entities,
+/// once per frame for entities scrolled in several groups
+pub fn scroll_behavior_system(
+    mut scrolled: EventReader<Scrolled>,
+    mut behaviors: Query<(&ScrollBehavior, &mut Transform)>,
+) {
+    let mut handled = HashSet::new();
+    for evt in scrolled.read() {
+        if !handled.insert(evt.entity) {
+            continue;
+        }
+        let Ok((behavior, mut transform)) = behaviors.get_mut(evt.entity) else {
+            continue;
+        };
+        match *behavior {
+            ScrollBehavior::Scale { min, max, speed } => {
+                let scale = (transform.scale.x * (1.0 + speed).powf(evt.delta.y)).clamp(min, max);
+                transform.scale = Vec3::new(scale, scale, transform.scale.z);
+            }
+            ScrollBehavior::Rotate { speed } => {
+                transform.rotate_z(evt.delta.y * speed);
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use bevy::gizmos::config::GizmoConfigStore;
+use bevy::prelude::*;
+
+use super::spatial::world_bounds;
+use crate::{Group, Interactable, InteractionSet, InteractionState, PointerId};
+
+/// How far apart the points of a lasso are at least, in world units
+const LASSO_POINT_SPACING: f32 = 4.0;
+
+/// The selection plugin lets a pointer drag a marquee over empty space, where no interactable
+/// of `MarqueeSelection::group` is hit. On release the interactables of the group whose shapes
+/// intersect the marquee become `Selected`. Holding Shift adds to the selection and holding
+/// Ctrl toggles the selection of the intersected interactables, otherwise the selection
+/// of the group is replaced. Pressing on empty space without dragging clears it.
+/// The marquee is drawn with gizmos if the `GizmoPlugin` is added.
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionState>()
+            .init_resource::<MarqueeSelection>()
+            .init_resource::<Marquee>()
+            .add_systems(
+                Update,
+                (
+                    marquee_selection_system.after(InteractionSet),
+                    marquee_gizmo_system
+                        .after(marquee_selection_system)
+                        .run_if(resource_exists::<GizmoConfigStore>),
+                ),
+            );
+    }
+}
+
+/// Added to the entities selected with a marquee
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selected;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarqueeShape {
+    /// A rectangle spanned by the press and the pointer position
+    #[default]
+    Rectangle,
+    /// A freeform outline following the pointer, closed between its last and first point
+    Lasso,
+}
+
+/// How a marquee changes the current selection, decided by the modifier keys held on press
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    #[default]
+    Replace,
+    Add,
+    Toggle,
+}
+
+impl SelectionMode {
+    /// The mode of the held modifier keys, Ctrl toggles and Shift adds
+    pub fn from_keys(keys: &ButtonInput<KeyCode>) -> Self {
+        if keys.any_pressed([
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+        ]) {
+            Self::Toggle
+        } else if keys.any_pressed([
+            KeyCode::ShiftLeft,
+            KeyCode::ShiftRight,
+        ]) {
+            Self::Add
+        } else {
+            Self::Replace
+        }
+    }
+
+    /// Whether an entity picked in this mode ends up selected
+    pub fn select(self, is_selected: bool) -> bool {
+        match self {
+            Self::Replace | Self::Add => true,
+            Self::Toggle => !is_selected,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct MarqueeSelection {
+    /// The group whose interactables are selected
+    pub group: Group,
+    pub shape: MarqueeShape,
+    /// The mouse button dragging the marquee, touches drag it if it is `MouseButton::Left`
+    pub button: MouseButton,
+    /// How far in world units the pointer has to move before a press turns into a marquee
+    pub min_distance: f32,
+    pub color: Color,
+}
+
+impl Default for MarqueeSelection {
+    fn default() -> Self {
+        Self {
+            group: Group::default(),
+            shape: MarqueeShape::Rectangle,
+            button: MouseButton::Left,
+            min_distance: 4.0,
+            color: Color::srgba(1.0, 1.0, 1.0, 0.8),
+        }
+    }
+}
+
+/// The marquee the selection plugin is dragging
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Marquee {
+    drag: Option<MarqueeDrag>,
+}
+
+#[derive(Debug, Clone)]
+struct MarqueeDrag {
+    pointer: PointerId,
+    button: MouseButton,
+    mode: SelectionMode,
+    shape: MarqueeShape,
+    /// The world positions of the pointer, the press and the last position for a rectangle
+    points: Vec<Vec2>,
+    /// Whether the pointer moved far enough from the press to drag a marquee
+    dragging: bool,
+}
+
+impl Marquee {
+    /// Whether a marquee is being dragged
+    pub fn is_dragging(&self) -> bool {
+        self.drag.as_ref().is_some_and(|drag| drag.dragging)
+    }
+
+    /// The pointer pressed on empty space, whether it drags a marquee yet or not
+    pub fn pointer(&self) -> Option<PointerId> {
+        self.drag.as_ref().map(|drag| drag.pointer)
+    }
+
+    pub fn mode(&self) -> Option<SelectionMode> {
+        self.drag.as_ref().map(|drag| drag.mode)
+    }
+
+    /// The closed outline of the marquee in world space, `None` while no marquee is dragged
+    pub fn outline(&self) -> Option<Vec<Vec2>> {
+        let drag = self.drag.as_ref().filter(|drag| drag.dragging)?;
+        match drag.shape {
+            MarqueeShape::Rectangle => {
+                let (start, end) = (drag.points[0], *drag.points.last()?);
+                Some(vec![
+                    start,
+                    Vec2::new(end.x, start.y),
+                    end,
+                    Vec2::new(start.x, end.y),
+                ])
+            }
+            MarqueeShape::Lasso => Some(drag.points.clone()),
+        }
+    }
+}
+
+/// Starts a marquee when a pointer presses on empty space, follows the pointer
+/// and selects the intersected interactables when it is released
+#[allow(clippy::too_many_arguments)]
+pub fn marquee_selection_system(
+    interaction_state: Res<InteractionState>,
+    settings: Res<MarqueeSelection>,
+    mut marquee: ResMut<Marquee>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
+    interactables: Query<(Entity, &Interactable, &GlobalTransform, Has<Selected>)>,
+    mut commands: Commands,
+) {
+    let group = settings.group;
+
+    if marquee.drag.is_none() {
+        let pressed = mouse_button_input
+            .just_pressed(settings.button)
+            .then_some(PointerId::Mouse)
+            .into_iter()
+            .chain(
+                touches
+                    .iter_just_pressed()
+                    .filter(|_| settings.button == MouseButton::Left)
+                    .map(|touch| PointerId::Touch(touch.id())),
+            );
+        for pointer in pressed {
+            let Some(position) = interaction_state.pointer_position(pointer, group) else {
+                continue;
+            };
+            if !interaction_state
+                .get_pointer_group(pointer, group)
+                .is_empty()
+            {
+                continue;
+            }
+            let mode = SelectionMode::from_keys(&keys);
+            if mode == SelectionMode::Replace {
+                for (entity, interactable, _, is_selected) in interactables.iter() {
+                    if is_selected && interactable.groups.contains(group) {
+                        commands.entity(entity).remove::<Selected>();
+                    }
+                }
+            }
+            marquee.drag = Some(MarqueeDrag {
+                pointer,
+                button: settings.button,
+                mode,
+                shape: settings.shape,
+                points: vec![position],
+                dragging: false,
+            });
+            break;
+        }
+    }
+
+    let Some(drag) = marquee.drag.as_mut() else {
+        return;
+    };
+    if let Some(position) = interaction_state.pointer_position(drag.pointer, group) {
+        let start = drag.points[0];
+        let last = *drag.points.last().unwrap_or(&start);
+        drag.dragging |= position.distance(start) >= settings.min_distance;
+        match drag.shape {
+            MarqueeShape::Rectangle => drag.points = vec![start, position],
+            MarqueeShape::Lasso => {
+                if position.distance(last) >= LASSO_POINT_SPACING {
+                    drag.points.push(position);
+                }
+            }
+        }
+    }
+
+    let released = match drag.pointer {
+        PointerId::Mouse => mouse_button_input.just_released(drag.button),
+        PointerId::Touch(id) => touches.just_released(id) || touches.just_canceled(id),
+    };
+    if !released {
+        return;
+    }
+    let mode = drag.mode;
+    let outline = marquee.outline();
+    marquee.drag = None;
+    let Some(outline) = outline else {
+        return;
+    };
+    let outline_bounds = outline
+        .iter()
+        .fold(Rect::from_corners(outline[0], outline[0]), |rect, point| {
+            rect.union_point(*point)
+        });
+
+    for (entity, interactable, global_transform, is_selected) in interactables.iter() {
+        if !interactable.enabled || !interactable.groups.contains(group) {
+            continue;
+        }
+        if world_bounds(global_transform, interactable)
+            .intersect(outline_bounds)
+            .is_empty()
+        {
+            continue;
+        }
+        // The shape is tested in the local space of the entity, where it is defined
+        let to_local = global_transform.affine().inverse();
+        let z = global_transform.translation().z;
+        let local_outline: Vec<Vec2> = outline
+            .iter()
+            .map(|point| to_local.transform_point3(point.extend(z)).truncate())
+            .collect();
+        if !interactable.shape.intersects_polygon(&local_outline) {
+            continue;
+        }
+        match (is_selected, mode.select(is_selected)) {
+            (false, true) => {
+                commands.entity(entity).insert(Selected);
+            }
+            (true, false) => {
+                commands.entity(entity).remove::<Selected>();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn marquee_gizmo_system(
+    marquee: Res<Marquee>,
+    settings: Res<MarqueeSelection>,
+    mut gizmos: Gizmos,
+) {
+    if let Some(outline) = marquee.outline() {
+        let closed = outline.iter().chain(outline.first()).copied();
+        gizmos.linestrip_2d(closed, settings.color);
+    }
+}
@@ -0,0 +1,281 @@
+use bevy::prelude::*;
+
+/// The interaction area of an interactable entity, in the entity's local space
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractionShape {
+    /// An axis aligned box given by its min and max corners
+    Aabb {
+        min: Vec2,
+        max: Vec2,
+    },
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// A closed polygon given by its vertices in order. Concave polygons are
+    /// supported, self-intersecting ones use the even-odd rule.
+    Polygon(Vec<Vec2>),
+    /// A point is inside a compound shape if it is inside any of its shapes
+    Compound(Vec<InteractionShape>),
+}
+
+impl Default for InteractionShape {
+    fn default() -> Self {
+        Self::Aabb {
+            min: Vec2::ZERO,
+            max: Vec2::ZERO,
+        }
+    }
+}
+
+impl From<(Vec2, Vec2)> for InteractionShape {
+    fn from((min, max): (Vec2, Vec2)) -> Self {
+        Self::Aabb { min, max }
+    }
+}
+
+impl InteractionShape {
+    /// A box of the given size centered on the entity
+    pub fn rect(size: Vec2) -> Self {
+        Self::Aabb {
+            min: -size / 2.0,
+            max: size / 2.0,
+        }
+    }
+
+    pub fn circle(radius: f32) -> Self {
+        Self::Circle {
+            center: Vec2::ZERO,
+            radius,
+        }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Aabb { min, max } => {
+                (min.x..max.x).contains(&point.x) && (min.y..max.y).contains(&point.y)
+            }
+            Self::Circle { center, radius } => point.distance_squared(*center) < radius * radius,
+            Self::Polygon(vertices) => polygon_contains(vertices, point),
+            Self::Compound(shapes) => shapes.iter().any(|shape| shape.contains(point)),
+        }
+    }
+
+    /// The smallest axis aligned rectangle containing the shape
+    pub fn bounds(&self) -> Rect {
+        match self {
+            Self::Aabb { min, max } => Rect {
+                min: *min,
+                max: *max,
+            },
+            Self::Circle { center, radius } => {
+                Rect::from_center_half_size(*center, Vec2::splat(*radius))
+            }
+            Self::Polygon(vertices) => vertices
+                .iter()
+                .fold(empty_rect(), |rect, vertex| rect.union_point(*vertex)),
+            Self::Compound(shapes) => shapes
+                .iter()
+                .fold(empty_rect(), |rect, shape| rect.union(shape.bounds())),
+        }
+    }
+
+    /// Whether the shape overlaps a closed polygon given in the same space.
+    /// Touching or containing the polygon counts as overlapping.
+    pub fn intersects_polygon(&self, polygon: &[Vec2]) -> bool {
+        match self {
+            Self::Aabb { min, max } => polygons_intersect(
+                &[
+                    *min,
+                    Vec2::new(max.x, min.y),
+                    *max,
+                    Vec2::new(min.x, max.y),
+                ],
+                polygon,
+            ),
+            Self::Circle { center, radius } => {
+                polygon_contains(polygon, *center)
+                    || edges(polygon).any(|(start, end)| {
+                        segment_distance_squared(start, end, *center) <= radius * radius
+                    })
+            }
+            Self::Polygon(vertices) => polygons_intersect(vertices, polygon),
+            Self::Compound(shapes) => shapes.iter().any(|shape| shape.intersects_polygon(polygon)),
+        }
+    }
+}
+
+fn empty_rect() -> Rect {
+    Rect {
+        min: Vec2::INFINITY,
+        max: Vec2::NEG_INFINITY,
+    }
+}
+
+/// Even-odd ray casting test along the positive x axis
+fn polygon_contains(vertices: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    let mut previous = match vertices.last() {
+        Some(vertex) => *vertex,
+        None => return false,
+    };
+    for vertex in vertices {
+        if (vertex.y > point.y) != (previous.y > point.y) {
+            let crossing_x =
+                vertex.x + (point.y - vertex.y) * (previous.x - vertex.x) / (previous.y - vertex.y);
+            if point.x < crossing_x {
+                inside = !inside;
+            }
+        }
+        previous = *vertex;
+    }
+    inside
+}
+
+/// The edges of a closed polygon as pairs of vertices
+fn edges(vertices: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(start, end)| (*start, *end))
+}
+
+fn polygons_intersect(a: &[Vec2], b: &[Vec2]) -> bool {
+    a.iter().any(|vertex| polygon_contains(b, *vertex))
+        || b.iter().any(|vertex| polygon_contains(a, *vertex))
+        || edges(a).any(|(a_start, a_end)| {
+            edges(b).any(|(b_start, b_end)| segments_intersect(a_start, a_end, b_start, b_end))
+        })
+}
+
+fn segments_intersect(a_start: Vec2, a_end: Vec2, b_start: Vec2, b_end: Vec2) -> bool {
+    let a = a_end - a_start;
+    let b = b_end - b_start;
+    let denominator = a.perp_dot(b);
+    if denominator == 0.0 {
+        // Parallel segments only touch through their end points, which are tested for
+        // containment already
+        return false;
+    }
+    let offset = b_start - a_start;
+    let t = offset.perp_dot(b) / denominator;
+    let u = offset.perp_dot(a) / denominator;
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
+
+fn segment_distance_squared(start: Vec2, end: Vec2, point: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 {
+        ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance_squared(start + segment * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A U shape opening upwards, its notch spans x 1..2 above y 1
+    const U_SHAPE: [Vec2; 8] = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(3.0, 0.0),
+        Vec2::new(3.0, 3.0),
+        Vec2::new(2.0, 3.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 3.0),
+        Vec2::new(0.0, 3.0),
+    ];
+
+    #[test]
+    fn concave_polygon_contains() {
+        assert!(polygon_contains(&U_SHAPE, Vec2::new(0.5, 2.5)));
+        assert!(polygon_contains(&U_SHAPE, Vec2::new(2.5, 2.5)));
+        assert!(polygon_contains(&U_SHAPE, Vec2::new(1.5, 0.5)));
+        assert!(!polygon_contains(&U_SHAPE, Vec2::new(1.5, 2.0)));
+        assert!(!polygon_contains(&U_SHAPE, Vec2::new(-0.5, 1.0)));
+        assert!(!polygon_contains(&[], Vec2::ZERO));
+    }
+
+    #[test]
+    fn circle_overlaps_a_lasso() {
+        let circle = InteractionShape::circle(1.0);
+        // The lasso crosses the circle without a vertex in it or the center inside it
+        let crossing = [
+            Vec2::new(-3.0, 0.5),
+            Vec2::new(3.0, 0.5),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(-3.0, 3.0),
+        ];
+        assert!(circle.intersects_polygon(&crossing));
+        // A lasso around the whole circle
+        let around = [
+            Vec2::new(-2.0, -2.0),
+            Vec2::new(2.0, -2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(-2.0, 2.0),
+        ];
+        assert!(circle.intersects_polygon(&around));
+        let beside = [
+            Vec2::new(1.5, -1.0),
+            Vec2::new(3.0, -1.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(1.5, 1.0),
+        ];
+        assert!(!circle.intersects_polygon(&beside));
+    }
+
+    #[test]
+    fn crossing_shapes_intersect_without_contained_vertices() {
+        let bar = InteractionShape::rect(Vec2::new(4.0, 1.0));
+        let lasso = [
+            Vec2::new(-0.5, -2.0),
+            Vec2::new(0.5, -2.0),
+            Vec2::new(0.5, 2.0),
+            Vec2::new(-0.5, 2.0),
+        ];
+        assert!(bar.intersects_polygon(&lasso));
+        // The lasso reaches into the notch of the U without touching it
+        let notch = [
+            Vec2::new(1.2, 1.5),
+            Vec2::new(1.8, 1.5),
+            Vec2::new(1.8, 4.0),
+            Vec2::new(1.2, 4.0),
+        ];
+        assert!(!InteractionShape::Polygon(U_SHAPE.to_vec()).intersects_polygon(&notch));
+    }
+
+    #[test]
+    fn segments_intersect_only_within_both() {
+        let origin = Vec2::ZERO;
+        assert!(segments_intersect(
+            origin,
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(2.0, 0.0),
+        ));
+        // Touching at an end point
+        assert!(segments_intersect(
+            origin,
+            Vec2::new(2.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+        ));
+        // The lines cross outside of the segments
+        assert!(!segments_intersect(
+            origin,
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 4.0),
+            Vec2::new(4.0, 0.0),
+        ));
+        assert!(!segments_intersect(
+            origin,
+            Vec2::new(2.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(2.0, 1.0),
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::layers::GroupMask;
+use crate::{Group, Interactable};
+
+/// Entities spanning more cells than this are kept in a per group list
+/// that every query checks, instead of being added to all of their cells.
+const MAX_CELLS_PER_ENTITY: i64 = 64;
+
+/// A uniform grid over the world space bounds of all interactables, used as a broad phase
+/// so the interaction system only tests the entities near each pointer.
+#[derive(Resource)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<(Group, IVec2), Vec<Entity>>,
+    oversized: HashMap<Group, Vec<Entity>>,
+    entries: HashMap<Entity, Entry>,
+}
+
+struct Entry {
+    groups: GroupMask,
+    /// The covered cells, `None` if the entity is oversized
+    cells: Option<IRect>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(128.0)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            oversized: HashMap::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of indexed entities
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds or moves an entity with the given world space bounds
+    pub fn insert(&mut self, entity: Entity, groups: &GroupMask, bounds: Rect) {
+        self.remove(entity);
+        if !bounds.min.is_finite() || !bounds.max.is_finite() || bounds.is_empty() {
+            return;
+        }
+        let cells = IRect {
+            min: self.cell(bounds.min),
+            max: self.cell(bounds.max),
+        };
+        let cell_count = (cells.width() as i64 + 1) * (cells.height() as i64 + 1);
+        let oversized = cell_count > MAX_CELLS_PER_ENTITY;
+        for group in groups.iter() {
+            if oversized {
+                self.oversized.entry(group).or_default().push(entity);
+                continue;
+            }
+            for y in cells.min.y..=cells.max.y {
+                for x in cells.min.x..=cells.max.x {
+                    self.cells
+                        .entry((group, IVec2::new(x, y)))
+                        .or_default()
+                        .push(entity);
+                }
+            }
+        }
+        self.entries.insert(
+            entity,
+            Entry {
+                groups: groups.clone(),
+                cells: (!oversized).then_some(cells),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        let Some(entry) = self.entries.remove(&entity) else {
+            return;
+        };
+        for group in entry.groups.iter() {
+            let Some(cells) = entry.cells else {
+                if let Some(list) = self.oversized.get_mut(&group) {
+                    list.retain(|e| *e != entity);
+                }
+                continue;
+            };
+            for y in cells.min.y..=cells.max.y {
+                for x in cells.min.x..=cells.max.x {
+                    let key = (group, IVec2::new(x, y));
+                    if let Some(list) = self.cells.get_mut(&key) {
+                        list.retain(|e| *e != entity);
+                        if list.is_empty() {
+                            self.cells.remove(&key);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The entities of `group` whose bounds may contain `point`
+    pub fn query(&self, group: Group, point: Vec2) -> impl Iterator<Item = Entity> + '_ {
+        let cell = self.cells.get(&(group, self.cell(point)));
+        let oversized = self.oversized.get(&group);
+        cell.into_iter()
+            .chain(oversized)
+            .flat_map(|list| list.iter().copied())
+    }
+
+    fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+}
+
+/// The world space bounds of an interactable
+pub fn world_bounds(global_transform: &GlobalTransform, interactable: &Interactable) -> Rect {
+    let local = interactable.shape.bounds();
+    let affine = global_transform.affine();
+    [
+        local.min,
+        local.max,
+        Vec2::new(local.min.x, local.max.y),
+        Vec2::new(local.max.x, local.min.y),
+    ]
+    .into_iter()
+    .map(|corner| affine.transform_point3(corner.extend(0.0)).truncate())
+    .fold(
+        Rect {
+            min: Vec2::INFINITY,
+            max: Vec2::NEG_INFINITY,
+        },
+        |rect, corner| rect.union_point(corner),
+    )
+}
+
+type MovedOrChanged = Or<(Changed<GlobalTransform>, Changed<Interactable>)>;
+
+/// This system keeps the `SpatialIndex` up to date with moved, changed and removed interactables
+pub fn spatial_index_system(
+    mut index: ResMut<SpatialIndex>,
+    changed: Query<(Entity, &GlobalTransform, &Interactable), MovedOrChanged>,
+    mut removed: RemovedComponents<Interactable>,
+) {
+    for entity in removed.read() {
+        index.remove(entity);
+    }
+    for (entity, global_transform, interactable) in changed.iter() {
+        index.insert(
+            entity,
+            &interactable.groups,
+            world_bounds(global_transform, interactable),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(index: &SpatialIndex, group: Group, point: Vec2) -> Vec<Entity> {
+        index.query(group, point).collect()
+    }
+
+    #[test]
+    fn moved_entity_leaves_its_old_cells() {
+        let mut index = SpatialIndex::new(10.0);
+        let entity = Entity::from_raw(1);
+        let groups = GroupMask::from(Group(0));
+
+        // The bounds span the four cells around the origin
+        index.insert(entity, &groups, Rect::new(-5.0, -5.0, 5.0, 5.0));
+        assert_eq!(query(&index, Group(0), Vec2::new(-3.0, 3.0)), [entity]);
+        assert_eq!(query(&index, Group(0), Vec2::new(3.0, -3.0)), [entity]);
+        assert!(query(&index, Group(1), Vec2::new(3.0, 3.0)).is_empty());
+        assert_eq!(index.cells.len(), 4);
+
+        index.insert(entity, &groups, Rect::new(102.0, 2.0, 108.0, 8.0));
+        assert!(query(&index, Group(0), Vec2::new(-3.0, 3.0)).is_empty());
+        assert!(query(&index, Group(0), Vec2::new(3.0, 3.0)).is_empty());
+        assert_eq!(query(&index, Group(0), Vec2::new(105.0, 5.0)), [entity]);
+        assert_eq!(index.cells.len(), 1);
+        assert_eq!(index.len(), 1);
+
+        index.remove(entity);
+        assert!(query(&index, Group(0), Vec2::new(105.0, 5.0)).is_empty());
+        assert!(index.cells.is_empty());
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn oversized_entity_is_returned_by_every_query_of_its_groups() {
+        let mut index = SpatialIndex::new(10.0);
+        let small = Entity::from_raw(1);
+        let large = Entity::from_raw(2);
+        let groups = GroupMask::from(Group(0));
+
+        index.insert(small, &groups, Rect::new(0.0, 0.0, 5.0, 5.0));
+        // 100 by 100 cells, more than `MAX_CELLS_PER_ENTITY`
+        index.insert(large, &groups, Rect::new(0.0, 0.0, 1000.0, 1000.0));
+        assert_eq!(index.cells.len(), 1);
+        assert_eq!(query(&index, Group(0), Vec2::new(2.0, 2.0)), [small, large]);
+        assert_eq!(query(&index, Group(0), Vec2::new(-500.0, 0.0)), [large]);
+        assert!(query(&index, Group(1), Vec2::new(2.0, 2.0)).is_empty());
+
+        // Shrinking moves it into the grid
+        index.insert(large, &groups, Rect::new(20.0, 20.0, 25.0, 25.0));
+        assert!(query(&index, Group(0), Vec2::new(-500.0, 0.0)).is_empty());
+        assert_eq!(query(&index, Group(0), Vec2::new(22.0, 22.0)), [large]);
+
+        index.insert(large, &groups, Rect::new(0.0, 0.0, 1000.0, 1000.0));
+        index.remove(large);
+        assert!(query(&index, Group(0), Vec2::new(-500.0, 0.0)).is_empty());
+        assert_eq!(query(&index, Group(0), Vec2::new(2.0, 2.0)), [small]);
+    }
+
+    #[test]
+    fn empty_or_infinite_bounds_are_not_indexed() {
+        let mut index = SpatialIndex::new(10.0);
+        let groups = GroupMask::from(Group(0));
+        index.insert(Entity::from_raw(1), &groups, Rect::new(5.0, 5.0, 5.0, 5.0));
+        index.insert(
+            Entity::from_raw(2),
+            &groups,
+            Rect {
+                min: Vec2::NEG_INFINITY,
+                max: Vec2::ZERO,
+            },
+        );
+        assert!(index.is_empty());
+        assert!(query(&index, Group(0), Vec2::new(5.0, 5.0)).is_empty());
+    }
+}
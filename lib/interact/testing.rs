@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::render::camera::{camera_system, ManualTextureViews};
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::{PrimaryWindow, WindowCreated, WindowResolution};
+
+use crate::{DragPlugin, Interactable, InteractionPlugin, InteractionShape, InteractionSource};
+
+/// The time every update of a headless app advances
+pub const HEADLESS_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// The headless plugin adds what the interaction plugins need to run without a GPU
+/// or a real window: input, transforms, hierarchy, image assets and camera viewports.
+/// Time advances by `HEADLESS_FRAME_TIME` per update, so timing is deterministic.
+/// Windows are spawned with `spawn_headless_window`.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            bevy::window::WindowPlugin {
+                primary_window: None,
+                ..default()
+            },
+            bevy::input::InputPlugin,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+        ))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlasLayout>()
+        .init_resource::<ManualTextureViews>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(HEADLESS_FRAME_TIME))
+        .add_systems(
+            PostUpdate,
+            camera_system::<OrthographicProjection>.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Spawns a primary window with the given logical size, without a window backend
+pub fn spawn_headless_window(world: &mut World, size: Vec2) -> Entity {
+    let window = world
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(size.x, size.y),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    world.send_event(WindowCreated { window });
+    window
+}
+
+/// An app running the `InteractionPlugin` and `DragPlugin` headless, with a 800x600 window
+/// and a `Camera2d` interaction source of the default group centered on the world origin.
+/// The input helpers send the same events as a real mouse or touch screen and update the app once,
+/// so their effect can be checked right after.
+pub struct InteractionTestApp {
+    pub app: App,
+    pub window: Entity,
+    pub camera: Entity,
+}
+
+impl Default for InteractionTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InteractionTestApp {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugin, InteractionPlugin, DragPlugin));
+        let window = spawn_headless_window(app.world_mut(), Vec2::new(800.0, 600.0));
+        let camera = app
+            .world_mut()
+            .spawn((Camera2d, InteractionSource::default()))
+            .id();
+        app.update();
+        Self {
+            app,
+            window,
+            camera,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Spawns an entity, it takes part in the interactions from the next update
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.world_mut().spawn(bundle).id()
+    }
+
+    /// Spawns an interactable entity of the default group at `position`
+    /// and updates the app once, so it can be hit right away
+    pub fn spawn_interactable(&mut self, shape: InteractionShape, position: Vec2) -> Entity {
+        let entity = self.spawn((
+            Transform::from_translation(position.extend(0.0)),
+            Interactable { shape, ..default() },
+        ));
+        self.step(1);
+        entity
+    }
+
+    /// The events of type `E` sent in the last two updates
+    pub fn events<E: Event + Clone>(&self) -> Vec<E> {
+        let events = self.world().resource::<Events<E>>();
+        events.get_cursor().read(events).cloned().collect()
+    }
+
+    /// Moves the mouse cursor over `world_position` as seen by the camera
+    pub fn move_cursor_to(&mut self, world_position: Vec2) -> &mut Self {
+        let position = self
+            .world_to_screen(world_position)
+            .expect("World position can't be seen by the camera");
+        self.move_cursor_to_screen(position)
+    }
+
+    /// Moves the mouse cursor to a logical window position
+    pub fn move_cursor_to_screen(&mut self, screen_position: Vec2) -> &mut Self {
+        let window = self.window;
+        self.world_mut().send_event(CursorMoved {
+            window,
+            position: screen_position,
+            delta: None,
+        });
+        self.step(1)
+    }
+
+    /// Moves the mouse cursor out of the window
+    pub fn leave_window(&mut self) -> &mut Self {
+        let window = self.window;
+        self.world_mut().send_event(CursorLeft { window });
+        self.step(1)
+    }
+
+    /// Sends the `phase` of the touch of finger `id` at a logical window position
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, screen_position: Vec2) -> &mut Self {
+        let window = self.window;
+        self.world_mut().send_event(TouchInput {
+            phase,
+            position: screen_position,
+            window,
+            force: None,
+            id,
+        });
+        self.step(1)
+    }
+
+    pub fn press(&mut self, button: MouseButton) -> &mut Self {
+        self.send_button(button, ButtonState::Pressed)
+    }
+
+    pub fn release(&mut self, button: MouseButton) -> &mut Self {
+        self.send_button(button, ButtonState::Released)
+    }
+
+    /// Presses and releases `button` in the next two updates
+    pub fn click(&mut self, button: MouseButton) -> &mut Self {
+        self.press(button).release(button)
+    }
+
+    /// Updates the app `frames` times
+    pub fn step(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.app.update();
+        }
+        self
+    }
+
+    /// The logical window position of `world_position` as seen by the camera
+    pub fn world_to_screen(&mut self, world_position: Vec2) -> Option<Vec2> {
+        let world = self.app.world_mut();
+        let (camera, global_transform) = world
+            .query::<(&Camera, &GlobalTransform)>()
+            .get(world, self.camera)
+            .ok()?;
+        let viewport_origin = camera
+            .logical_viewport_rect()
+            .map(|rect| rect.min)
+            .unwrap_or_default();
+        camera
+            .world_to_viewport(global_transform, world_position.extend(0.0))
+            .ok()
+            .map(|position| position + viewport_origin)
+    }
+
+    fn send_button(&mut self, button: MouseButton, state: ButtonState) -> &mut Self {
+        let window = self.window;
+        self.world_mut().send_event(MouseButtonInput {
+            button,
+            state,
+            window,
+        });
+        self.step(1)
+    }
+}
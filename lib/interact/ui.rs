@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy::render::camera::NormalizedRenderTarget;
+use bevy::ui::{CalculatedClip, DefaultUiCamera, FocusPolicy};
+use bevy::window::PrimaryWindow;
+
+use crate::{InteractionState, PointerId};
+
+/// Decides whether a UI node blocks world interactions under it.
+/// Nodes without this component block if their `FocusPolicy` is `Block`, like buttons do.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiBlocking {
+    /// Pointers over the node don't hit any interactable in the world
+    #[default]
+    Block,
+    /// Pointers pass through the node, even if its `FocusPolicy` is `Block`
+    PassThrough,
+}
+
+type UiNode = (
+    &'static ComputedNode,
+    &'static GlobalTransform,
+    &'static ViewVisibility,
+    Option<&'static CalculatedClip>,
+    Option<&'static TargetCamera>,
+    Option<&'static FocusPolicy>,
+    Option<&'static UiBlocking>,
+);
+
+/// This system finds the pointers that are over a blocking UI node.
+/// Their world hits are suppressed by the interaction system for this frame,
+/// their world positions are kept so running drags continue under the UI.
+pub(crate) fn ui_blocking_system(
+    mut interaction_state: ResMut<InteractionState>,
+    nodes: Query<UiNode>,
+    cameras: Query<&Camera>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    default_ui_camera: DefaultUiCamera,
+) {
+    interaction_state.blocked_pointers.clear();
+    if nodes.is_empty() {
+        return;
+    }
+    let primary_window = primary_window.get_single().ok();
+    let default_ui_camera = default_ui_camera.get();
+
+    let mut blocked = HashSet::new();
+    for (node, global_transform, view_visibility, clip, target_camera, focus_policy, blocking) in
+        nodes.iter()
+    {
+        let blocks = match blocking {
+            Some(blocking) => *blocking == UiBlocking::Block,
+            None => focus_policy == Some(&FocusPolicy::Block),
+        };
+        if !blocks || !view_visibility.get() {
+            continue;
+        }
+        let Some(camera) = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera)
+            .and_then(|camera| cameras.get(camera).ok())
+        else {
+            continue;
+        };
+        let Some(NormalizedRenderTarget::Window(window_ref)) =
+            camera.target.normalize(primary_window)
+        else {
+            continue;
+        };
+        let window_entity = window_ref.entity();
+        let Ok(window) = windows.get(window_entity) else {
+            continue;
+        };
+
+        // Node rects are in physical pixels relative to the camera viewport
+        let node_rect =
+            Rect::from_center_size(global_transform.translation().truncate(), node.size());
+        let visible_rect = clip
+            .map(|clip| node_rect.intersect(clip.clip))
+            .unwrap_or(node_rect);
+        let viewport_origin = camera
+            .physical_viewport_rect()
+            .map(|rect| rect.min.as_vec2())
+            .unwrap_or_default();
+        let contains = |logical_position: Vec2| {
+            visible_rect.contains(logical_position * window.scale_factor() - viewport_origin)
+        };
+
+        if interaction_state
+            .window_cursor_positions
+            .get(&window_entity)
+            .is_some_and(|position| contains(*position))
+        {
+            blocked.insert(PointerId::Mouse);
+        }
+        for (id, (window, position)) in interaction_state.window_touch_positions.iter() {
+            if *window == window_entity && contains(*position) {
+                blocked.insert(PointerId::Touch(*id));
+            }
+        }
+    }
+    interaction_state.blocked_pointers = blocked;
+}
@@ -5,9 +5,33 @@ pub mod world;
 
 pub use assets::{AssetsPlugin, Retro2dAssets};
 pub use config::AppState;
+pub use interact::cursor::InteractionCursor;
+pub use interact::debug::{InteractionDebug, InteractionDebugPlugin};
 pub use interact::drag::{DragPlugin, Draggable, Dragged, DropStrategy};
+pub use interact::drop::{
+    DragEnter, DragLeave, DragOver, DropDetection, DropPredicate, DropZone, DropZoneState, Dropped,
+};
+pub use interact::error::{InteractionError, InteractionErrorPolicy};
+pub use interact::events::{
+    Clicked, DoubleClicked, HoverEnter, HoverExit, LongPressed, PointerEventSettings, Pressed,
+    Released, Scrolled,
+};
+pub use interact::gamepad::{GamepadCursor, GamepadCursorIndicator, GamepadCursorPlugin};
+pub use interact::replay::{
+    InputRecorder, InputRecorderPlugin, InputRecording, InputReplay, RecordedButton, RecordedEvent,
+    RecordedInput, RecordedTouchPhase,
+};
+pub use interact::selection::{
+    Marquee, MarqueeSelection, MarqueeShape, Selected, SelectionMode, SelectionPlugin,
+};
+#[cfg(any(test, feature = "testing"))]
+pub use interact::testing::InteractionTestApp;
 pub use interact::{
-    interact::Group, interact::Interactable, interact::InteractionPlugin,
-    interact::InteractionSource, interact::InteractionState,
+    alpha::HitMode, interact::world_to_local, interact::Group, interact::Interactable,
+    interact::InteractionPlugin, interact::InteractionSet, interact::InteractionSource,
+    interact::InteractionState, interact::PickPolicy, interact::PointerId, interact::SourceView,
+    layers::GroupMask, layers::InteractionLayers, layers::InteractionLayersAppExt,
+    markers::Hovered, markers::PressedBy, scroll::ScrollBehavior, shape::InteractionShape,
+    spatial::SpatialIndex, ui::UiBlocking,
 };
 pub use world::WorldPlugin;
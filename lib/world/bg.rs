@@ -1,6 +1,12 @@
 use crate::assets::{ImageAsset, Retro2dAssets};
+use crate::world::clothes::ITEM_LAYER;
+use crate::{DropDetection, DropZone, Dropped, InteractionLayers};
 use bevy::prelude::*;
 
+/// The basket of the background image in its pixel coordinates, centered on the image
+const BASKET_MIN: Vec2 = Vec2::new(-245.0, -842.0);
+const BASKET_MAX: Vec2 = Vec2::new(274.0, -626.0);
+
 #[derive(Component)]
 pub struct Background;
 
@@ -9,8 +15,10 @@ pub fn setup_background(
     retro2d_assets: Res<Retro2dAssets>,
     windows: Query<&Window>,
     assets: Res<Assets<Image>>,
+    layers: Res<InteractionLayers>,
 ) {
     let window = windows.single();
+    let item_group = layers.get(ITEM_LAYER).expect("Item layer not registered");
     let background = ImageAsset::new(retro2d_assets.cows_and_basket.clone(), &assets);
     let window_aspect_ratio = window.width() / window.height();
     let image_width = background.width;
@@ -27,5 +35,20 @@ pub fn setup_background(
         Sprite::from_image(background.handle.clone()),
         Transform::from_xyz(0.0, 0.0, 0.0).with_scale(Vec3::new(scale, scale, 1.0)),
         Background,
+        // The items only move horizontally, so the pointer has to reach the basket for them
+        DropZone::new((BASKET_MIN, BASKET_MAX).into())
+            .with_groups(item_group)
+            .with_detection(DropDetection::Pointer),
     ));
 }
+
+pub fn drop_into_basket(
+    mut dropped: EventReader<Dropped>,
+    backgrounds: Query<(), With<Background>>,
+) {
+    for evt in dropped.read() {
+        if backgrounds.contains(evt.zone) {
+            info!("Item {} dropped into the basket", evt.dragged);
+        }
+    }
+}
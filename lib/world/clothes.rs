@@ -1,10 +1,18 @@
 use crate::assets::{ImageAsset, Retro2dAssets};
-use crate::{Draggable, DropStrategy, Group, Interactable, InteractionSource, InteractionState};
+use crate::{
+    Clicked, Draggable, Dragged, DropStrategy, GamepadCursorIndicator, HitMode, Hovered,
+    Interactable, InteractionCursor, InteractionLayers, InteractionShape, InteractionSource,
+    MarqueeSelection, PickPolicy, Scrolled, Selected, SelectionMode,
+};
 use bevy::prelude::*;
+use std::collections::HashMap;
 
-const ITEM_GROUP: u8 = 1;
+pub const BACKGROUND_LAYER: &str = "background";
+pub const ITEM_LAYER: &str = "items";
 const ROPE_SPACING: f32 = 400.0;
 const NUM_ROPES: i32 = 9;
+/// How far the rope carousel moves per scrolled line
+const CAROUSEL_SCROLL_SPEED: f32 = 40.0;
 
 #[derive(Component)]
 pub struct Rope {
@@ -20,47 +28,60 @@ pub struct ItemState {
     pub is_glowing: bool,
     pub is_dragging: bool,
     pub is_selected: bool,
+    /// Whether the item actions were opened with a right click
+    pub is_showing_actions: bool,
 }
 
 pub fn interact_with_no_hover(
-    interaction_state: Res<InteractionState>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut items: Query<(Entity, &mut ItemState)>,
+    hovered_items: Query<(), (With<ItemState>, With<Hovered>)>,
+    mut items: Query<&mut ItemState>,
 ) {
-    let mut no_hover = false;
-    for (entity, _) in items.iter_mut() {
-        let is_hovered = interaction_state
-            .get_group(Group(ITEM_GROUP))
-            .iter()
-            .any(|(e, _)| *e == entity);
-        no_hover |= is_hovered;
-    }
-    if !no_hover && mouse_button_input.just_pressed(MouseButton::Left) {
-        for (_, mut state) in items.iter_mut() {
-            state.is_selected = false;
+    // The selection is cleared or extended by the marquee selection
+    if hovered_items.is_empty() && mouse_button_input.just_pressed(MouseButton::Left) {
+        for mut state in items.iter_mut() {
             state.is_glowing = false;
+            state.is_showing_actions = false;
         }
     }
 }
 
+type ItemSelection = (
+    Entity,
+    &'static mut ItemState,
+    &'static mut Sprite,
+    Has<Hovered>,
+    Has<Selected>,
+);
+
 pub fn interact_with_items(
-    interaction_state: Res<InteractionState>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut items: Query<(Entity, &mut ItemState, &mut Sprite)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut items: Query<ItemSelection>,
+    mut commands: Commands,
 ) {
-    for (entity, mut state, mut sprite) in items.iter_mut() {
-        let is_hovered = interaction_state
-            .get_group(Group(ITEM_GROUP))
-            .iter()
-            .any(|(e, _)| *e == entity);
-
-        // selection
-        if !state.is_selected && mouse_button_input.just_pressed(MouseButton::Left) && is_hovered {
-            state.is_selected = true;
-            sprite.image = state.selected.handle.clone();
-        } else if mouse_button_input.just_pressed(MouseButton::Left) && !is_hovered {
-            state.is_selected = false;
-            sprite.image = state.normal.handle.clone();
+    let selection_mode = SelectionMode::from_keys(&keys);
+    for (entity, mut state, mut sprite, is_hovered, is_selected) in items.iter_mut() {
+        // selection, clicks pick items with the same modifiers as the marquee
+        if mouse_button_input.just_pressed(MouseButton::Left) {
+            let selected = if is_hovered {
+                selection_mode.select(is_selected)
+            } else {
+                is_selected && selection_mode != SelectionMode::Replace
+            };
+            if selected && !is_selected {
+                commands.entity(entity).insert(Selected);
+            } else if !selected && is_selected {
+                commands.entity(entity).remove::<Selected>();
+            }
+        }
+        if state.is_selected != is_selected {
+            state.is_selected = is_selected;
+            sprite.image = if is_selected {
+                state.selected.handle.clone()
+            } else {
+                state.normal.handle.clone()
+            };
         }
         // dragging
         if mouse_button_input.pressed(MouseButton::Left) && !state.is_dragging && is_hovered {
@@ -79,13 +100,41 @@ pub fn interact_with_items(
     }
 }
 
+/// Observes the clicks of an item, including the ones bubbled up from its child sprites
+pub fn open_item_actions(mut trigger: Trigger<Clicked>, mut items: Query<&mut ItemState>) {
+    if trigger.event().button != MouseButton::Right {
+        return;
+    }
+    let item = trigger.entity();
+    if let Ok(mut state) = items.get_mut(item) {
+        trigger.propagate(false);
+        state.is_showing_actions = !state.is_showing_actions;
+        info!(
+            "Item actions of {} {}",
+            item,
+            if state.is_showing_actions {
+                "opened"
+            } else {
+                "closed"
+            }
+        );
+    }
+}
+
 pub fn setup_clothes(
     mut commands: Commands,
     retro2d_assets: Res<Retro2dAssets>,
     assets: Res<Assets<Image>>,
     windows: Query<&Window>,
+    layers: Res<InteractionLayers>,
+    mut marquee_selection: ResMut<MarqueeSelection>,
 ) {
     let window = windows.single();
+    let background_group = layers
+        .get(BACKGROUND_LAYER)
+        .expect("Background layer not registered");
+    let item_group = layers.get(ITEM_LAYER).expect("Item layer not registered");
+    marquee_selection.group = item_group;
     let window_width = window.width();
 
     let hoodie = ImageAsset::new(retro2d_assets.hoodie.clone(), &assets);
@@ -98,32 +147,52 @@ pub fn setup_clothes(
         is_glowing: false,
         is_dragging: false,
         is_selected: false,
+        is_showing_actions: false,
     };
 
     let interactable = Interactable {
-        groups: vec![Group(ITEM_GROUP)],
-        bounding_box: (
+        groups: item_group.into(),
+        shape: (
             Vec2::new(-hoodie.width / 2.0, -hoodie.height / 2.0),
             Vec2::new(hoodie.width / 2.0, hoodie.height / 2.0),
-        ),
+        )
+            .into(),
+        hit_mode: HitMode::alpha(),
+        pick_policy: Some(PickPolicy::Topmost),
+        buttons: vec![
+            MouseButton::Left,
+            MouseButton::Right,
+        ],
+        ..Default::default()
     };
 
     let draggable = Draggable {
-        groups: vec![Group(ITEM_GROUP)],
+        groups: item_group.into(),
         hook: None,
         drop_strategy: DropStrategy::Leave,
         lock_y: true,
+        buttons: vec![MouseButton::Left],
     };
 
     // Setup camera
     commands.spawn(Camera2d).insert(InteractionSource {
-        groups: vec![
-            Group(0), // BG_GROUP
-            Group(ITEM_GROUP),
-        ],
-        ..Default::default()
+        groups: [background_group, item_group].into(),
     });
 
+    // Cursor for playing with a gamepad
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(12.0),
+            height: Val::Px(12.0),
+            ..Default::default()
+        },
+        BackgroundColor(Color::WHITE),
+        BorderRadius::MAX,
+        Visibility::Hidden,
+        GamepadCursorIndicator,
+    ));
+
     // Spawn hoodie as parent entity
     let hoodie_entity = commands
         .spawn((
@@ -138,14 +207,16 @@ pub fn setup_clothes(
             hoodie_state.clone(),
             interactable.clone(),
             draggable.clone(),
+            InteractionCursor::grab(),
         ))
+        .observe(open_item_actions)
         .id();
 
     // Calculate rope offset based on hoodie height
     let rope_offset = hoodie.height / 2.0 - 20.;
 
     // Create multiple ropes across the screen
-    let start_x = -(window_width as f32 / 2.0) * ROPE_SPACING;
+    let start_x = -(window_width / 2.0) * ROPE_SPACING;
 
     // Spawn ropes
     commands
@@ -165,6 +236,14 @@ pub fn setup_clothes(
                 };
                 parent.spawn((
                     rope_sprite,
+                    // Ropes are only hovered for scrolling the carousel, they take no
+                    // buttons and stay in the background group, out of the item selection
+                    Interactable {
+                        groups: background_group.into(),
+                        shape: InteractionShape::rect(Vec2::new(rope.width, rope.height)),
+                        buttons: vec![],
+                        ..Default::default()
+                    },
                     Transform {
                         translation: Vec3::new(x_pos, rope_offset, 1.0),
                         ..Default::default()
@@ -178,6 +257,28 @@ pub fn setup_clothes(
         });
 }
 
+/// Scrolling over an item or one of its ropes moves the item along the rope carousel
+pub fn scroll_rope_carousel(
+    mut scrolled: EventReader<Scrolled>,
+    ropes: Query<&Rope>,
+    mut items: Query<&mut Transform, (With<ItemState>, Without<Dragged>)>,
+) {
+    // An item and its rope can both be scrolled in the same frame
+    let mut scrolled_items = HashMap::new();
+    for evt in scrolled.read() {
+        let item = ropes
+            .get(evt.entity)
+            .map(|rope| rope.attached_to)
+            .unwrap_or(evt.entity);
+        scrolled_items.insert(item, evt.delta.y);
+    }
+    for (item, delta) in scrolled_items {
+        if let Ok(mut transform) = items.get_mut(item) {
+            transform.translation.x += delta * CAROUSEL_SCROLL_SPEED;
+        }
+    }
+}
+
 pub fn update_rope_position(
     mut ropes: Query<(&mut Transform, &Rope)>,
     items: Query<&Transform, (With<ItemState>, Without<Rope>)>,
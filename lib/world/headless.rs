@@ -0,0 +1,48 @@
+use crate::assets::Retro2dAssets;
+use crate::config::AppState;
+use crate::interact::testing::{spawn_headless_window, HeadlessPlugin};
+use crate::WorldPlugin;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+
+/// Builds the game without a renderer or a real window, for replaying recorded input
+/// with an `InputReplay`. The primary window has the size of the native window and
+/// the images are decoded from the `assets` folder, so hits match the real game.
+/// The game state is entered on the first update.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin, StatesPlugin))
+        .insert_state(AppState::AssetsLoading)
+        .add_plugins(WorldPlugin);
+    spawn_headless_window(app.world_mut(), Vec2::new(1280.0, 900.0));
+
+    let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+    let retro2d_assets = Retro2dAssets {
+        cows_and_basket: images.add(load_image("cows_and_basket.png")),
+        hoodie: images.add(load_image("hoodie.png")),
+        hoodie_glow: images.add(load_image("hoodie_glow.png")),
+        hoodie_selected: images.add(load_image("hoodie_selected.png")),
+        transparent_rope: images.add(load_image("transparent_rope.png")),
+    };
+    app.insert_resource(retro2d_assets);
+    app.world_mut()
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    app
+}
+
+fn load_image(name: &str) -> Image {
+    let path = format!("{}/assets/{name}", env!("CARGO_MANIFEST_DIR"));
+    let bytes = std::fs::read(&path).unwrap_or_else(|err| panic!("Failed to read {path}: {err}"));
+    Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .unwrap_or_else(|err| panic!("Failed to decode {path}: {err}"))
+}
@@ -1,5 +1,11 @@
 mod bg;
 mod clothes;
+#[cfg(any(test, feature = "testing"))]
+mod headless;
 mod plugin;
 
+pub use bg::Background;
+pub use clothes::{ItemState, Rope};
+#[cfg(any(test, feature = "testing"))]
+pub use headless::headless_app;
 pub use plugin::WorldPlugin;
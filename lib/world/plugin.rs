@@ -1,12 +1,23 @@
+use crate::world::clothes::{BACKGROUND_LAYER, ITEM_LAYER};
 use crate::AppState;
-use crate::{DragPlugin, InteractionPlugin};
+use crate::{
+    DragPlugin, GamepadCursorPlugin, InteractionLayersAppExt, InteractionPlugin, InteractionSet,
+    SelectionPlugin,
+};
 use bevy::prelude::*;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((InteractionPlugin, DragPlugin));
+        app.add_plugins((
+            InteractionPlugin,
+            DragPlugin,
+            GamepadCursorPlugin,
+            SelectionPlugin,
+        ))
+        .register_interaction_layer(BACKGROUND_LAYER)
+        .register_interaction_layer(ITEM_LAYER);
         app.add_systems(
             OnExit(AppState::AssetsLoading),
             crate::world::bg::setup_background,
@@ -20,8 +31,11 @@ impl Plugin for WorldPlugin {
             (
                 crate::world::clothes::interact_with_no_hover,
                 crate::world::clothes::interact_with_items,
+                crate::world::clothes::scroll_rope_carousel,
                 crate::world::clothes::update_rope_position,
+                crate::world::bg::drop_into_basket,
             )
+                .after(InteractionSet)
                 .run_if(in_state(AppState::Game)),
         );
     }
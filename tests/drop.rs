@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use retro_2d_lib::{
+    Draggable, Dragged, DropDetection, DropStrategy, DropZone, Dropped, InteractionShape,
+    InteractionTestApp,
+};
+
+#[derive(Component)]
+struct Fragile;
+
+fn translation(app: &InteractionTestApp, entity: Entity) -> Vec2 {
+    app.world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+        .truncate()
+}
+
+fn assert_near(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-3),
+        "{actual} is not {expected}"
+    );
+}
+
+fn spawn_draggable(app: &mut InteractionTestApp, draggable: Draggable) -> Entity {
+    let entity = app.spawn_interactable(InteractionShape::rect(Vec2::splat(50.0)), Vec2::ZERO);
+    app.world_mut().entity_mut(entity).insert(draggable);
+    entity
+}
+
+fn spawn_zone(app: &mut InteractionTestApp, zone: DropZone, position: Vec2) -> Entity {
+    let entity = app.spawn((Transform::from_translation(position.extend(0.0)), zone));
+    app.step(1);
+    entity
+}
+
+/// Drags the entity at the world origin to `target` and releases it there
+fn drag_to(app: &mut InteractionTestApp, target: Vec2) {
+    app.move_cursor_to(Vec2::ZERO)
+        .press(MouseButton::Left)
+        .move_cursor_to(target)
+        .release(MouseButton::Left);
+}
+
+#[test]
+fn drop_onto_a_zone() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(
+        &mut app,
+        Draggable {
+            drop_strategy: DropStrategy::Reset,
+            ..default()
+        },
+    );
+    let zone = spawn_zone(
+        &mut app,
+        DropZone::new(InteractionShape::rect(Vec2::splat(100.0))),
+        Vec2::new(200.0, 0.0),
+    );
+
+    drag_to(&mut app, Vec2::new(210.0, 10.0));
+    let dropped = app.events::<Dropped>();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].dragged, entity);
+    assert_eq!(dropped[0].zone, zone);
+    assert_near(dropped[0].position, Vec2::new(210.0, 10.0));
+
+    // The drop replaces the `DropStrategy::Reset` of the draggable
+    assert!(app.world().get::<Dragged>(entity).is_none());
+    assert_near(translation(&app, entity), Vec2::new(210.0, 10.0));
+}
+
+#[test]
+fn rejected_drop_falls_back_to_reset() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(
+        &mut app,
+        Draggable {
+            drop_strategy: DropStrategy::Reset,
+            ..default()
+        },
+    );
+    spawn_zone(
+        &mut app,
+        DropZone::new(InteractionShape::rect(Vec2::splat(100.0)))
+            .with_predicate(|dragged| dragged.contains::<Fragile>()),
+        Vec2::new(200.0, 0.0),
+    );
+
+    drag_to(&mut app, Vec2::new(210.0, 10.0));
+    assert!(app.events::<Dropped>().is_empty());
+    assert!(app.world().get::<Dragged>(entity).is_none());
+    assert_near(translation(&app, entity), Vec2::ZERO);
+}
+
+#[test]
+fn pointer_detection_drops_a_locked_entity() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(
+        &mut app,
+        Draggable {
+            lock_y: true,
+            ..default()
+        },
+    );
+    let zone = spawn_zone(
+        &mut app,
+        DropZone::new(InteractionShape::rect(Vec2::splat(100.0)))
+            .with_detection(DropDetection::Pointer),
+        Vec2::new(200.0, 200.0),
+    );
+
+    // The entity stays on the x axis, only the pointer reaches the zone
+    drag_to(&mut app, Vec2::new(200.0, 200.0));
+    let dropped = app.events::<Dropped>();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].zone, zone);
+    assert_near(translation(&app, entity), Vec2::new(200.0, 0.0));
+}
@@ -0,0 +1,310 @@
+use bevy::input::touch::TouchPhase;
+use bevy::prelude::*;
+use retro_2d_lib::{
+    Clicked, DoubleClicked, Draggable, Dragged, DropStrategy, Group, HoverEnter, HoverExit,
+    Hovered, Interactable, InteractionShape, InteractionSource, InteractionTestApp, LongPressed,
+    PointerId, PressedBy, Released,
+};
+
+fn translation(app: &InteractionTestApp, entity: Entity) -> Vec2 {
+    app.world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+        .truncate()
+}
+
+fn assert_near(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-3),
+        "{actual} is not {expected}"
+    );
+}
+
+fn spawn_square(app: &mut InteractionTestApp, position: Vec2) -> Entity {
+    app.spawn_interactable(InteractionShape::rect(Vec2::splat(50.0)), position)
+}
+
+fn spawn_draggable(app: &mut InteractionTestApp, draggable: Draggable) -> Entity {
+    let entity = spawn_square(app, Vec2::ZERO);
+    app.world_mut().entity_mut(entity).insert(draggable);
+    entity
+}
+
+#[test]
+fn hover_enter_and_exit() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_square(&mut app, Vec2::new(100.0, 0.0));
+
+    app.move_cursor_to(Vec2::new(100.0, 10.0));
+    let hovered = app.world().get::<Hovered>(entity).unwrap();
+    assert_eq!(hovered.pointer, PointerId::Mouse);
+    assert_near(hovered.local_pos, Vec2::new(0.0, 10.0));
+    assert!(app
+        .events::<HoverEnter>()
+        .iter()
+        .any(|event| event.entity == entity));
+
+    app.move_cursor_to(Vec2::new(-100.0, 0.0));
+    assert!(app.world().get::<Hovered>(entity).is_none());
+    assert!(app
+        .events::<HoverExit>()
+        .iter()
+        .any(|event| event.entity == entity));
+}
+
+#[test]
+fn hover_exit_when_leaving_the_window() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_square(&mut app, Vec2::ZERO);
+
+    app.move_cursor_to(Vec2::ZERO);
+    assert!(app.world().get::<Hovered>(entity).is_some());
+
+    app.leave_window();
+    assert!(app.world().get::<Hovered>(entity).is_none());
+    assert!(app
+        .events::<HoverExit>()
+        .iter()
+        .any(|event| event.entity == entity));
+}
+
+#[test]
+fn drag_start_move_and_release() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(&mut app, Draggable::default());
+
+    app.move_cursor_to(Vec2::new(10.0, 10.0))
+        .press(MouseButton::Left);
+    let dragged = app.world().get::<Dragged>(entity).unwrap();
+    assert_eq!(dragged.pointer, PointerId::Mouse);
+    assert_eq!(dragged.origin, Vec2::ZERO);
+
+    // The entity keeps the offset to the cursor it had when the drag started
+    app.move_cursor_to(Vec2::new(110.0, 60.0));
+    assert_near(translation(&app, entity), Vec2::new(100.0, 50.0));
+
+    app.release(MouseButton::Left);
+    assert!(app.world().get::<Dragged>(entity).is_none());
+
+    // The default `DropStrategy::Leave` keeps the entity where it was dropped
+    app.move_cursor_to(Vec2::new(200.0, 0.0));
+    assert_near(translation(&app, entity), Vec2::new(100.0, 50.0));
+}
+
+#[test]
+fn drop_strategy_reset_moves_back_to_the_origin() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(
+        &mut app,
+        Draggable {
+            drop_strategy: DropStrategy::Reset,
+            ..default()
+        },
+    );
+
+    app.move_cursor_to(Vec2::ZERO)
+        .press(MouseButton::Left)
+        .move_cursor_to(Vec2::new(150.0, -80.0));
+    assert_near(translation(&app, entity), Vec2::new(150.0, -80.0));
+
+    app.release(MouseButton::Left);
+    assert!(app.world().get::<Dragged>(entity).is_none());
+    assert_near(translation(&app, entity), Vec2::ZERO);
+}
+
+#[test]
+fn lock_y_only_follows_the_cursor_horizontally() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(
+        &mut app,
+        Draggable {
+            lock_y: true,
+            ..default()
+        },
+    );
+
+    app.move_cursor_to(Vec2::ZERO)
+        .press(MouseButton::Left)
+        .move_cursor_to(Vec2::new(120.0, 90.0));
+    assert_near(translation(&app, entity), Vec2::new(120.0, 0.0));
+
+    app.release(MouseButton::Left);
+    assert_near(translation(&app, entity), Vec2::new(120.0, 0.0));
+}
+
+#[test]
+fn touch_drag_start_move_and_release() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_draggable(&mut app, Draggable::default());
+    let center = app.world_to_screen(Vec2::ZERO).unwrap();
+
+    app.touch(3, TouchPhase::Started, center);
+    let dragged = app.world().get::<Dragged>(entity).unwrap();
+    assert_eq!(dragged.pointer, PointerId::Touch(3));
+
+    // Screen space points down, so the entity moves up by 40 in the world
+    app.touch(3, TouchPhase::Moved, center + Vec2::new(60.0, 40.0));
+    assert_near(translation(&app, entity), Vec2::new(60.0, -40.0));
+
+    app.touch(3, TouchPhase::Ended, center + Vec2::new(60.0, 40.0));
+    assert!(app.world().get::<Dragged>(entity).is_none());
+    assert_near(translation(&app, entity), Vec2::new(60.0, -40.0));
+}
+
+#[test]
+fn click_and_double_click() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_square(&mut app, Vec2::ZERO);
+
+    app.move_cursor_to(Vec2::ZERO).click(MouseButton::Left);
+    let clicked = app.events::<Clicked>();
+    assert_eq!(clicked.len(), 1);
+    assert_eq!(clicked[0].entity, entity);
+    assert_eq!(clicked[0].button, MouseButton::Left);
+    assert!(app.events::<DoubleClicked>().is_empty());
+
+    app.click(MouseButton::Left);
+    let double_clicked = app.events::<DoubleClicked>();
+    assert_eq!(double_clicked.len(), 1);
+    assert_eq!(double_clicked[0].entity, entity);
+
+    // The third click starts a new double click
+    app.step(2).click(MouseButton::Left);
+    assert!(app.events::<DoubleClicked>().is_empty());
+}
+
+#[test]
+fn double_click_is_too_slow() {
+    let mut app = InteractionTestApp::new();
+    spawn_square(&mut app, Vec2::ZERO);
+
+    app.move_cursor_to(Vec2::ZERO)
+        .click(MouseButton::Left)
+        .step(30)
+        .click(MouseButton::Left);
+    assert_eq!(app.events::<Clicked>().len(), 1);
+    assert!(app.events::<DoubleClicked>().is_empty());
+}
+
+#[test]
+fn double_click_every_hit_entity() {
+    let mut app = InteractionTestApp::new();
+    let below = spawn_square(&mut app, Vec2::ZERO);
+    let above = spawn_square(&mut app, Vec2::new(10.0, 0.0));
+
+    app.move_cursor_to(Vec2::new(5.0, 0.0))
+        .click(MouseButton::Left)
+        .click(MouseButton::Left);
+    let mut double_clicked: Vec<Entity> = app
+        .events::<DoubleClicked>()
+        .iter()
+        .map(|event| event.entity)
+        .collect();
+    double_clicked.sort();
+    let mut expected = vec![below, above];
+    expected.sort();
+    assert_eq!(double_clicked, expected);
+}
+
+#[test]
+fn double_click_every_hit_group() {
+    let mut app = InteractionTestApp::new();
+    let groups = [Group(0), Group(1)];
+    let camera = app.camera;
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(InteractionSource {
+            groups: groups.into(),
+        });
+    let entity = spawn_square(&mut app, Vec2::ZERO);
+    app.world_mut()
+        .get_mut::<Interactable>(entity)
+        .unwrap()
+        .groups = groups.into();
+
+    app.move_cursor_to(Vec2::ZERO)
+        .click(MouseButton::Left)
+        .click(MouseButton::Left);
+    let mut double_clicked: Vec<Group> = app
+        .events::<DoubleClicked>()
+        .iter()
+        .filter(|event| event.entity == entity)
+        .map(|event| event.group)
+        .collect();
+    double_clicked.sort_by_key(|group| group.0);
+    assert_eq!(double_clicked, groups);
+}
+
+#[test]
+fn long_press_instead_of_a_click() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_square(&mut app, Vec2::ZERO);
+
+    app.move_cursor_to(Vec2::ZERO).press(MouseButton::Left);
+    let mut long_pressed = vec![];
+    // The default long press time is half a second, 30 updates
+    for _ in 0..40 {
+        app.step(1);
+        long_pressed.extend(app.events::<LongPressed>());
+    }
+    long_pressed.dedup();
+    assert_eq!(long_pressed.len(), 1);
+    assert_eq!(long_pressed[0].entity, entity);
+    assert_eq!(long_pressed[0].button, MouseButton::Left);
+
+    app.release(MouseButton::Left);
+    assert!(app
+        .events::<Released>()
+        .iter()
+        .any(|event| event.entity == entity));
+    assert!(app.events::<Clicked>().is_empty());
+}
+
+#[test]
+fn pressed_while_any_button_is_held() {
+    let mut app = InteractionTestApp::new();
+    let entity = spawn_square(&mut app, Vec2::ZERO);
+
+    app.move_cursor_to(Vec2::ZERO).press(MouseButton::Left);
+    assert_eq!(
+        app.world().get::<PressedBy>(entity).unwrap().button,
+        MouseButton::Left
+    );
+    app.press(MouseButton::Right);
+    assert_eq!(
+        app.world().get::<PressedBy>(entity).unwrap().button,
+        MouseButton::Right
+    );
+
+    app.release(MouseButton::Right);
+    assert_eq!(
+        app.world().get::<PressedBy>(entity).unwrap().button,
+        MouseButton::Left
+    );
+    app.release(MouseButton::Left);
+    assert!(app.world().get::<PressedBy>(entity).is_none());
+}
+
+#[derive(Resource, Default)]
+struct ParentClicks(Vec<Entity>);
+
+#[test]
+fn click_bubbles_once_to_a_hit_parent() {
+    let mut app = InteractionTestApp::new();
+    let parent = spawn_square(&mut app, Vec2::ZERO);
+    let child = spawn_square(&mut app, Vec2::ZERO);
+    app.world_mut().entity_mut(child).set_parent(parent);
+    app.world_mut().init_resource::<ParentClicks>();
+    app.world_mut().entity_mut(parent).observe(
+        |trigger: Trigger<Clicked>, mut clicks: ResMut<ParentClicks>| {
+            clicks.0.push(trigger.event().entity);
+        },
+    );
+    app.step(1);
+
+    app.move_cursor_to(Vec2::ZERO).click(MouseButton::Left);
+    // Both are hit, but the parent handles the click of its child only
+    assert_eq!(app.events::<Clicked>().len(), 2);
+    assert_eq!(app.world().resource::<ParentClicks>().0, [child]);
+}
@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use retro_2d_lib::{Group, InteractionState, InteractionTestApp, SourceView};
+
+const WORLD_POINTS: [Vec2; 4] = [
+    Vec2::ZERO,
+    Vec2::new(37.5, -12.25),
+    Vec2::new(-120.0, 80.0),
+    Vec2::new(150.0, 140.0),
+];
+
+fn assert_near(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-3),
+        "{actual} is not {expected}"
+    );
+}
+
+/// Applies `setup` to the camera and returns its view once the camera and the
+/// interaction state have picked up the change
+fn source_view(
+    setup: impl FnOnce(&mut Camera, &mut OrthographicProjection, &mut Transform),
+) -> SourceView {
+    let mut app = InteractionTestApp::new();
+    let camera = app.camera;
+    let world = app.world_mut();
+    let (mut camera, mut projection, mut transform) = world
+        .query::<(&mut Camera, &mut OrthographicProjection, &mut Transform)>()
+        .get_mut(world, camera)
+        .unwrap();
+    setup(&mut camera, &mut projection, &mut transform);
+    // The camera is updated in `PostUpdate`, the interaction state reads it the update after
+    app.step(2);
+    app.world().resource::<InteractionState>().source_views[&Group::default()][0].clone()
+}
+
+fn assert_round_trips(view: &SourceView) {
+    for world_position in WORLD_POINTS {
+        let screen_position = view.world_to_screen(world_position).unwrap();
+        assert_near(
+            view.screen_to_world(screen_position).unwrap(),
+            world_position,
+        );
+    }
+}
+
+#[test]
+fn scaled_and_moved_camera() {
+    let view = source_view(|_, projection, transform| {
+        projection.scale = 2.0;
+        transform.translation = Vec3::new(30.0, -20.0, 0.0);
+    });
+
+    // The camera is centered in the 800x600 window, every pixel spans 2 world units
+    assert_near(
+        view.screen_to_world(Vec2::new(400.0, 300.0)).unwrap(),
+        Vec2::new(30.0, -20.0),
+    );
+    assert_near(
+        view.screen_to_world(Vec2::new(500.0, 250.0)).unwrap(),
+        Vec2::new(230.0, 80.0),
+    );
+    assert_round_trips(&view);
+}
+
+#[test]
+fn viewport_with_an_origin() {
+    let view = source_view(|camera, projection, _| {
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(100, 50),
+            physical_size: UVec2::new(400, 300),
+            ..default()
+        });
+        projection.scale = 0.5;
+    });
+
+    // The camera is centered in its viewport, not in the window
+    assert_near(
+        view.world_to_screen(Vec2::ZERO).unwrap(),
+        Vec2::new(300.0, 200.0),
+    );
+    assert_near(
+        view.screen_to_world(Vec2::new(100.0, 50.0)).unwrap(),
+        Vec2::new(-100.0, 75.0),
+    );
+    assert!(view.viewport_contains(Vec2::new(450.0, 300.0)));
+    assert!(!view.viewport_contains(Vec2::new(50.0, 300.0)));
+    assert_round_trips(&view);
+}
+
+#[test]
+fn fixed_vertical_scaling() {
+    let view = source_view(|_, projection, _| {
+        projection.scaling_mode = ScalingMode::FixedVertical {
+            viewport_height: 300.0,
+        };
+    });
+
+    // 300 world units span the 600 pixels of the window height
+    assert_near(
+        view.screen_to_world(Vec2::new(400.0, 0.0)).unwrap(),
+        Vec2::new(0.0, 150.0),
+    );
+    assert_near(
+        view.screen_to_world(Vec2::new(800.0, 300.0)).unwrap(),
+        Vec2::new(200.0, 0.0),
+    );
+    assert_round_trips(&view);
+}
+
+#[test]
+fn auto_min_scaling() {
+    let view = source_view(|_, projection, _| {
+        projection.scaling_mode = ScalingMode::AutoMin {
+            min_width: 400.0,
+            min_height: 400.0,
+        };
+    });
+
+    // The 800x600 window is limited by its height, it shows 400 world units of it
+    assert_near(
+        view.screen_to_world(Vec2::new(400.0, 0.0)).unwrap(),
+        Vec2::new(0.0, 200.0),
+    );
+    assert_near(
+        view.screen_to_world(Vec2::new(800.0, 300.0)).unwrap(),
+        Vec2::new(800.0 / 3.0, 0.0),
+    );
+    assert_round_trips(&view);
+}
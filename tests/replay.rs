@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use retro_2d_lib::world::{headless_app, Background, ItemState};
+use retro_2d_lib::{
+    Dragged, Dropped, InputRecording, InputReplay, RecordedButton, RecordedEvent, RecordedInput,
+    Selected,
+};
+
+const HOODIE_DRAG: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/recordings/hoodie_drag.json"
+);
+
+/// The hoodie is the only item of the game
+fn hoodie(app: &mut App) -> (Entity, Transform, ItemState) {
+    let world = app.world_mut();
+    let (entity, transform, state) = world
+        .query::<(Entity, &Transform, &ItemState)>()
+        .single(world);
+    (entity, *transform, state.clone())
+}
+
+/// A recording of `frames` frames at 60 fps sending each event in its frame
+fn recording(frames: usize, events: &[(usize, RecordedEvent)]) -> InputRecording {
+    InputRecording {
+        frame_times: vec![1.0 / 60.0; frames],
+        inputs: events
+            .iter()
+            .map(|&(frame, event)| RecordedInput {
+                frame,
+                time: frame as f32 / 60.0,
+                event,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn recording_survives_json() {
+    let recording = InputRecording::load(HOODIE_DRAG).unwrap();
+    let json = recording.to_json().unwrap();
+    assert_eq!(InputRecording::from_json(&json).unwrap(), recording);
+}
+
+#[test]
+fn replay_drags_and_selects_the_hoodie() {
+    let recording = InputRecording::load(HOODIE_DRAG).unwrap();
+    let mut app = headless_app();
+    let mut replay = InputReplay::new(recording);
+
+    // The cursor moves onto the hoodie, Shift and the left button are pressed on it
+    for _ in 0..5 {
+        replay.step(&mut app);
+    }
+    let (entity, _, state) = hoodie(&mut app);
+    assert!(app.world().get::<Dragged>(entity).is_some());
+    assert!(state.is_dragging);
+    assert!(app
+        .world()
+        .resource::<ButtonInput<KeyCode>>()
+        .pressed(KeyCode::ShiftLeft));
+
+    // The hoodie follows the cursor 200 pixels to the right, but keeps its height
+    replay.run(&mut app);
+    let (entity, transform, state) = hoodie(&mut app);
+    assert!(app.world().get::<Dragged>(entity).is_none());
+    assert!((transform.translation.x - 200.0).abs() < 1e-3);
+    assert_eq!(transform.translation.y, 0.0);
+    assert!(!state.is_dragging);
+    assert!(!app
+        .world()
+        .resource::<ButtonInput<KeyCode>>()
+        .pressed(KeyCode::ShiftLeft));
+    assert!(state.is_selected);
+    assert!(app.world().get::<Selected>(entity).is_some());
+}
+
+#[test]
+fn replay_drops_the_hoodie_into_the_basket() {
+    // The 2880x1800 background is scaled to the 1280 pixels of the window width,
+    // its basket is around 326 pixels below the window center
+    let mut replay = InputReplay::new(recording(
+        8,
+        &[
+            (
+                3,
+                RecordedEvent::CursorMoved {
+                    position: (640.0, 450.0),
+                },
+            ),
+            (
+                4,
+                RecordedEvent::MouseButton {
+                    button: RecordedButton::Left,
+                    pressed: true,
+                },
+            ),
+            (
+                5,
+                RecordedEvent::CursorMoved {
+                    position: (646.0, 776.0),
+                },
+            ),
+            (
+                7,
+                RecordedEvent::MouseButton {
+                    button: RecordedButton::Left,
+                    pressed: false,
+                },
+            ),
+        ],
+    ));
+    let mut app = headless_app();
+    replay.run(&mut app);
+
+    // The hoodie can't leave its rope, but the cursor dropped it into the basket
+    let (entity, transform, _) = hoodie(&mut app);
+    assert_eq!(transform.translation.y, 0.0);
+    let world = app.world_mut();
+    let background = world
+        .query_filtered::<Entity, With<Background>>()
+        .single(world);
+    let events = app.world().resource::<Events<Dropped>>();
+    let dropped: Vec<Dropped> = events.get_cursor().read(events).copied().collect();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].dragged, entity);
+    assert_eq!(dropped[0].zone, background);
+}
@@ -0,0 +1,86 @@
+use bevy::input::touch::TouchPhase;
+use bevy::prelude::*;
+use retro_2d_lib::{
+    Clicked, HoverExit, Hovered, InteractionShape, InteractionTestApp, PointerId, Pressed,
+    PressedBy,
+};
+
+#[test]
+fn touch_hovers_presses_and_clicks() {
+    let mut app = InteractionTestApp::new();
+    let entity = app.spawn_interactable(InteractionShape::rect(Vec2::splat(50.0)), Vec2::ZERO);
+    let center = app.world_to_screen(Vec2::ZERO).unwrap();
+    let pointer = PointerId::Touch(7);
+
+    app.touch(7, TouchPhase::Started, center);
+    let hovered = app.world().get::<Hovered>(entity).unwrap();
+    assert_eq!(hovered.pointer, pointer);
+    let pressed_by = app.world().get::<PressedBy>(entity).unwrap();
+    assert_eq!(pressed_by.pointer, pointer);
+    assert_eq!(pressed_by.button, MouseButton::Left);
+    assert!(app
+        .events::<Pressed>()
+        .iter()
+        .any(|event| event.entity == entity && event.pointer == pointer));
+
+    app.touch(7, TouchPhase::Moved, center + Vec2::new(5.0, 5.0));
+    assert_eq!(app.world().get::<Hovered>(entity).unwrap().pointer, pointer);
+
+    app.touch(7, TouchPhase::Ended, center + Vec2::new(5.0, 5.0));
+    assert!(app.world().get::<PressedBy>(entity).is_none());
+    assert!(app
+        .events::<Clicked>()
+        .iter()
+        .any(|event| event.entity == entity
+            && event.pointer == pointer
+            && event.button == MouseButton::Left));
+
+    // The finger is gone the update after it was lifted
+    app.step(1);
+    assert!(app.world().get::<Hovered>(entity).is_none());
+    assert!(app
+        .events::<HoverExit>()
+        .iter()
+        .any(|event| event.entity == entity && event.pointer == pointer));
+}
+
+#[test]
+fn canceled_touch_stops_hovering() {
+    let mut app = InteractionTestApp::new();
+    let entity = app.spawn_interactable(InteractionShape::rect(Vec2::splat(50.0)), Vec2::ZERO);
+    let center = app.world_to_screen(Vec2::ZERO).unwrap();
+
+    app.touch(2, TouchPhase::Started, center);
+    assert!(app.world().get::<Hovered>(entity).is_some());
+
+    app.touch(2, TouchPhase::Canceled, center).step(1);
+    assert!(app.world().get::<Hovered>(entity).is_none());
+    assert!(app.world().get::<PressedBy>(entity).is_none());
+}
+
+#[test]
+fn touches_hover_separately() {
+    let mut app = InteractionTestApp::new();
+    let entity = app.spawn_interactable(InteractionShape::rect(Vec2::splat(50.0)), Vec2::ZERO);
+    let center = app.world_to_screen(Vec2::ZERO).unwrap();
+
+    app.touch(1, TouchPhase::Started, center).touch(
+        2,
+        TouchPhase::Started,
+        center + Vec2::new(300.0, 0.0),
+    );
+    assert_eq!(
+        app.world().get::<Hovered>(entity).unwrap().pointer,
+        PointerId::Touch(1)
+    );
+
+    // Lifting the finger outside the entity leaves it unhovered
+    app.touch(2, TouchPhase::Ended, center + Vec2::new(300.0, 0.0))
+        .step(1);
+    assert_eq!(
+        app.world().get::<Hovered>(entity).unwrap().pointer,
+        PointerId::Touch(1)
+    );
+    app.touch(1, TouchPhase::Ended, center).step(1);
+    assert!(app.world().get::<Hovered>(entity).is_none());
+}